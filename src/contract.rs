@@ -4,13 +4,36 @@
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, OwnedDeps, Response, StdResult};
-use cw2::set_contract_version;
+use cosmwasm_std::{
+    to_binary, Addr, Api, Binary, Deps, DepsMut, Empty, Env, Event, MessageInfo, Order, OverflowError,
+    OverflowOperation, Response, StdError, StdResult, Storage, SubMsg,
+};
+use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
+use cw0::Expiration;
+use cw2::{get_contract_version, set_contract_version};
+use cw4::{
+    AdminResponse, HooksResponse, Member, MemberChangedHookMsg, MemberDiff, MemberListResponse, MemberResponse,
+    TotalWeightResponse,
+};
+use cw_storage_plus::{Bound, U64Key};
+use semver::Version;
+use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, HashResponse, InstantiateMsg, OwnerResponse, QueryMsg, ScoreFromAddressResponse};
-use crate::state::{State, STATE};
-use std::collections::HashMap;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, HasRoleResponse, HashResponse, HookEntry, InstantiateMsg, ListBoardsResponse,
+    ListHooksResponse, ListScoresResponse, MigrateMsg, OwnerResponse, PauseStatusResponse, PendingOwnerResponse, QueryMsg,
+    RankResponse, ResolveNameResponse, ReverseLookupResponse, RoleMembersResponse, ScoreAtHeightResponse,
+    ScoreChangedHookMsg, ScoreEntry, ScoreFromAddressResponse, ScoreHistoryResponse, ScoreOp, StateDigestResponse,
+    TotalAtHeightResponse,
+};
+use crate::state::{
+    digest_node_key, rank_count_key, rank_key, BoardInfo, Config, DigestChild, DigestNode, HistoryRecord, HookKind, KeyKind, PauseInfo,
+    PendingOwner, Profile, Role, State, BOARDS, BOARDS_NAMESPACE, CONFIG, DEFAULT_BOARD, DEFAULT_MAX_BATCH_SIZE, DEFAULT_MAX_HISTORY,
+    DIGEST_NODES, DIGEST_ROOT, ENTRY_SEQ, HISTORY, HISTORY_RANGE, HOOKS, KEY_KINDS, LEADERBOARD, LEGACY_STATE, MEMBERS, NAME_OF, NAMES,
+    NEXT_SEQ, PAUSE, PENDING_OWNER, PROFILES, RANK_COUNTS, ROLES, SCORES, STATE, TOTAL, TOTAL_KEY, TOTAL_WEIGHT,
+};
+use std::collections::{BTreeMap, BTreeSet};
 
 extern crate serde_derive;
 extern crate serde;
@@ -20,6 +43,28 @@ extern crate serde_json;
 const CONTRACT_NAME: &str = "crates.io:ethan-gnibus-smart-contract";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// The first version that stores scores in the SCORES Map.
+// Anything older keeps every score in LegacyState.hash.
+const SCORES_MAP_VERSION: &str = "0.2.0";
+
+// Page sizes for paginated queries.
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+// The longest memo a write may attach to its history record, in bytes.
+const MAX_MEMO_LENGTH: u32 = 256;
+
+// Size limits for profiles, in bytes unless noted.
+const MAX_DISPLAY_NAME_LENGTH: u32 = 64;
+const MAX_URI_LENGTH: u32 = 256;
+const MAX_PROFILE_TAGS: u32 = 16;
+const MAX_TAG_KEY_LENGTH: u32 = 32;
+const MAX_TAG_VALUE_LENGTH: u32 = 128;
+
+// Length limits for registered names, in bytes.
+const MIN_NAME_LENGTH: usize = 3;
+const MAX_NAME_LENGTH: usize = 32;
+
 // ======================================================================
 // Instantiate Block
 // ======================================================================
@@ -27,39 +72,196 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// Instantiate a smart contract.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    
-    // Use msg.first_address_score to make a JSON string that will
-    // hold the Key, Value pairs we will use to represent
-    // addresses and corresponding scores.
-    let address = msg.first_address;
-    let score = msg.first_address_score;
-    let mut hash: HashMap<String, i32> = HashMap::new();
-    hash.insert(address, score);
-    let hash = serde_json::to_string(&hash).unwrap().to_string();
+    // Set up the default board.
+    let (owner, count) = init_board(deps.branch(), &env, &info, msg)?;
+    let board = BoardInfo {
+        name: DEFAULT_BOARD.to_string(),
+        created_by: info.sender,
+        created_height: env.block.height,
+        closed_height: None,
+    };
+    BOARDS.save(deps.storage, DEFAULT_BOARD, &board)?;
+
+    // Save the contract version.
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("count", count.to_string())
+        .add_attribute("owner", owner)
+    )
+}
+
+/// Sets up the storage of a new board: validates msg up front, then saves
+/// the config, the initial scores and the owner. Returns the owner and the
+/// number of initial scores.
+fn init_board(deps: DepsMut, env: &Env, info: &MessageInfo, msg: InstantiateMsg) -> Result<(Addr, usize), ContractError> {
+    // Error if the owner is invalid.
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender.clone(),
+    };
+
+    // Error if the config is invalid.
+    let config = Config {
+        allow_external_keys: msg.allow_external_keys,
+        open_registration: msg.open_registration,
+        default_score: msg.default_score,
+        max_batch_size: msg.max_batch_size.unwrap_or(DEFAULT_MAX_BATCH_SIZE),
+        min_score: msg.min_score.unwrap_or(i32::MIN),
+        max_score: msg.max_score.unwrap_or(i32::MAX),
+        max_delta: msg.max_delta.unwrap_or(u32::MAX),
+        max_history: msg.max_history.unwrap_or(DEFAULT_MAX_HISTORY),
+    };
+    validate_config(&config)?;
+
+    // Error if any initial address or score is invalid, before anything is
+    // saved. Every address that appears more than once is named at once.
+    let mut initial_scores = msg.initial_scores;
+    if let Some(first_address) = msg.first_address {
+        initial_scores.insert(0, (first_address, msg.first_address_score));
+    }
+    let mut table = Vec::with_capacity(initial_scores.len());
+    let mut seen = BTreeSet::new();
+    let mut duplicates = BTreeSet::new();
+    for (address, score) in initial_scores {
+        let (address, kind) = validate_address(deps.api, &config, address)?;
+        check_score(&config, None, score)?;
+        if !seen.insert(address.clone()) {
+            duplicates.insert(address.clone());
+        }
+        table.push((address, kind, score));
+    }
+    if !duplicates.is_empty() {
+        return Err(ContractError::DuplicateAddresses { addresses: duplicates.into_iter().collect() });
+    }
+
+    // Save the config and store every initial (address, score) pair in the scores Map.
+    CONFIG.save(deps.storage, &config)?;
+    for (address, kind, score) in table.iter() {
+        append_history(deps.storage, env, &info.sender, address, None, Some(*score), None)?;
+        insert_score(deps.storage, address, *kind, *score, env.block.height)?;
+    }
 
     // Initialize state.
     let state = State {
-        hash: hash.clone(),
-        owner: info.sender.clone(),
+        owner: Some(owner.clone()),
     };
 
     // Save state.
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     STATE.save(deps.storage, &state)?;
 
+    Ok((owner, table.len()))
+}
+
+// ======================================================================
+// Migrate Block
+// ======================================================================
+
+/// Migrate a smart contract to the current version.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Error if the stored contract is not this contract.
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::CannotMigrate { previous_contract: stored.contract });
+    }
+
+    // Error if the stored version is newer than this version.
+    let previous_version = parse_version(&stored.version)?;
+    let new_version = parse_version(CONTRACT_VERSION)?;
+    if previous_version > new_version {
+        return Err(ContractError::CannotMigrateVersion {
+            previous_version: stored.version,
+            new_version: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    // Unpack the legacy JSON String into the scores Map if needed.
+    let mut migrated = 0;
+    if previous_version < parse_version(SCORES_MAP_VERSION)? {
+        migrated = migrate_legacy_state(deps.branch(), env.block.height)?;
+    }
+
+    // Make the existing data the default board. It already lives at the
+    // top level of storage, so only the board itself needs saving.
+    if !BOARDS.has(deps.storage, DEFAULT_BOARD) {
+        let board = BoardInfo {
+            name: DEFAULT_BOARD.to_string(),
+            created_by: env.contract.address.clone(),
+            created_height: env.block.height,
+            closed_height: None,
+        };
+        BOARDS.save(deps.storage, DEFAULT_BOARD, &board)?;
+    }
+
+    // Save the new version.
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     // Return response.
     Ok(Response::new()
-        .add_attribute("method", "instantiate")
-        .add_attribute("hash", hash)
-        .add_attribute("owner", info.sender)
+        .add_attribute("method", "migrate")
+        .add_attribute("previous_version", stored.version)
+        .add_attribute("new_version", CONTRACT_VERSION)
+        .add_attribute("migrated_scores", migrated.to_string())
     )
 }
 
+/// Moves every score in the legacy state into the scores Map and
+/// rewrites the state without them. Returns the number of scores moved.
+fn migrate_legacy_state(deps: DepsMut, height: u64) -> Result<usize, ContractError> {
+    // Load the legacy state.
+    let legacy = LEGACY_STATE.load(deps.storage)?;
+
+    // Deserialize the legacy HashMap from the JSON String. A BTreeMap
+    // is used so every node inserts the addresses in the same order.
+    let hash: BTreeMap<String, i32> = serde_json::from_str(&legacy.hash)
+        .map_err(|err| ContractError::InvalidLegacyState { reason: format!("cannot parse hash: {}", err) })?;
+
+    // Save every (address, score) pair to the scores Map under its normalized
+    // key. Legacy contracts accepted any key, so keys that are not valid
+    // addresses are kept as external identifiers. Legacy contracts also
+    // reported a score of 1 for unknown addresses, so that stays the default.
+    let mut config = Config {
+        allow_external_keys: false,
+        open_registration: false,
+        default_score: 1,
+        max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+        min_score: i32::MIN,
+        max_score: i32::MAX,
+        max_delta: u32::MAX,
+        max_history: DEFAULT_MAX_HISTORY,
+    };
+    for (address, score) in hash.iter() {
+        let (key, kind) = normalize_address(deps.api, address);
+        if SCORES.may_load(deps.storage, &key)?.is_some() {
+            return Err(ContractError::InvalidLegacyState { reason: format!("duplicate address after normalization: {}", key) });
+        }
+        config.allow_external_keys |= kind == KeyKind::External;
+        insert_score(deps.storage, &key, kind, *score, height)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    // Overwrite the legacy state with the new state.
+    let state = State {
+        owner: Some(legacy.owner),
+    };
+    STATE.save(deps.storage, &state)?;
+
+    Ok(hash.len())
+}
+
+/// Parses a contract version string into a comparable Version.
+fn parse_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version).map_err(|_| ContractError::InvalidVersion { version: version.to_string() })
+}
+
 // ======================================================================
 // Execute Block
 // ======================================================================
@@ -68,278 +270,3943 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // Error if the default board is paused and the msg is not scoped to a
+    // board. Every other msg is checked against its own board in execute_board.
+    if matches!(msg, ExecuteMsg::RegisterName { .. } | ExecuteMsg::CreateBoard { .. } | ExecuteMsg::CloseBoard { .. }) {
+        assert_not_paused(deps.storage)?;
+    }
+
+    match msg {
+        // When the msg matches CreateBoard, call try_create_board.
+        ExecuteMsg::CreateBoard { board, msg } => try_create_board(deps, env, info, board, msg),
+
+        // When the msg matches CloseBoard, call try_close_board.
+        ExecuteMsg::CloseBoard { board } => try_close_board(deps, env, info, board),
+
+        // When the msg matches RegisterName, call try_register_name.
+        ExecuteMsg::RegisterName { name } => try_register_name(deps, info, name),
+
+        // Otherwise run the msg against the storage of its board.
+        msg => {
+            // Error if the board does not exist or is closed.
+            let board = load_board(deps.storage, msg.board())?;
+            if board.closed_height.is_some() {
+                return Err(ContractError::BoardClosed { board: board.name });
+            }
+
+            // Scope the storage to the board.
+            let mut prefixed;
+            let storage: &mut dyn Storage = if board.name == DEFAULT_BOARD {
+                deps.storage
+            } else {
+                prefixed = PrefixedStorage::multilevel(deps.storage, &[BOARDS_NAMESPACE, board.name.as_bytes()]);
+                &mut prefixed
+            };
+            let deps = DepsMut { storage, api: deps.api, querier: deps.querier };
+
+            let res = execute_board(deps, env, info, msg)?;
+            Ok(res.add_attribute("board", board.name))
+        }
+    }
+}
+
+/// Execute a msg scoped to a board. deps only sees the storage of the board.
+fn execute_board(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    // Error if the board is paused, unless the msg unpauses it
+    // or recovers ownership.
+    if let Some(pause) = PAUSE.may_load(deps.storage)? {
+        let exempt = matches!(msg,
+            ExecuteMsg::Unpause { .. }
+            | ExecuteMsg::ProposeOwner { .. }
+            | ExecuteMsg::AcceptOwnership { .. }
+            | ExecuteMsg::CancelOwnershipProposal { .. }
+        );
+        if !exempt {
+            return Err(ContractError::ContractPaused { reason: pause.reason });
+        }
+    }
+
     match msg {
         // When the msg matches AddAddress, call try_add_address.
-        ExecuteMsg::AddAddress { new_address, new_score } => try_add_address(deps, info, new_address, new_score),
+        ExecuteMsg::AddAddress { new_address, new_score, memo, .. } => try_add_address(deps, env, info, new_address, new_score, memo),
 
         // When the msg matches Set, call try_set.
-        ExecuteMsg::Set { address, new_score } => try_set(deps, info, address, new_score),
+        ExecuteMsg::Set { address, new_score, memo, .. } => try_set(deps, env, info, address, new_score, memo),
+
+        // When the msg matches SetProfile, call try_set_profile.
+        ExecuteMsg::SetProfile { address, profile, .. } => try_set_profile(deps, info, address, profile),
+
+        // When the msg matches RemoveAddress, call try_remove_address.
+        ExecuteMsg::RemoveAddress { address, memo, .. } => try_remove_address(deps, env, info, address, memo),
+
+        // When the msg matches Increment, call try_apply_delta with Add.
+        ExecuteMsg::Increment { address, delta, register_missing, memo, .. } => {
+            try_apply_delta(deps, env, info, address, OverflowOperation::Add, delta, register_missing, memo)
+        }
+
+        // When the msg matches Decrement, call try_apply_delta with Sub.
+        ExecuteMsg::Decrement { address, delta, register_missing, memo, .. } => {
+            try_apply_delta(deps, env, info, address, OverflowOperation::Sub, delta, register_missing, memo)
+        }
+
+        // When the msg matches BatchUpdate, call try_batch_update.
+        ExecuteMsg::BatchUpdate { updates, .. } => try_batch_update(deps, env, info, updates),
+
+        // When the msg matches UpdateConfig, call try_update_config.
+        ExecuteMsg::UpdateConfig { open_registration, default_score, max_batch_size, min_score, max_score, max_delta, max_history, .. } => {
+            try_update_config(deps, info, open_registration, default_score, max_batch_size, min_score, max_score, max_delta, max_history)
+        }
+
+        // When the msg matches AddHook, call try_add_hook.
+        ExecuteMsg::AddHook { addr, kind, .. } => try_add_hook(deps, info, addr, kind),
+
+        // When the msg matches RemoveHook, call try_remove_hook.
+        ExecuteMsg::RemoveHook { addr, kind, .. } => try_remove_hook(deps, info, addr, kind),
+
+        // When the msg matches Pause, call try_pause.
+        ExecuteMsg::Pause { reason, .. } => try_pause(deps, env, info, reason),
+
+        // When the msg matches Unpause, call try_unpause.
+        ExecuteMsg::Unpause { .. } => try_unpause(deps, info),
+
+        // When the msg matches ProposeOwner, call try_propose_owner.
+        ExecuteMsg::ProposeOwner { new_owner, expiry, .. } => try_propose_owner(deps, env, info, new_owner, expiry),
+
+        // When the msg matches AcceptOwnership, call try_accept_ownership.
+        ExecuteMsg::AcceptOwnership { .. } => try_accept_ownership(deps, env, info),
+
+        // When the msg matches CancelOwnershipProposal, call try_cancel_ownership_proposal.
+        ExecuteMsg::CancelOwnershipProposal { .. } => try_cancel_ownership_proposal(deps, info),
+
+        // When the msg matches RenounceOwnership, call try_renounce_ownership.
+        ExecuteMsg::RenounceOwnership { .. } => try_renounce_ownership(deps, info),
+
+        // When the msg matches GrantRole, call try_grant_role.
+        ExecuteMsg::GrantRole { role, address, .. } => try_grant_role(deps, info, role, address),
+
+        // When the msg matches RevokeRole, call try_revoke_role.
+        ExecuteMsg::RevokeRole { role, address, .. } => try_revoke_role(deps, info, role, address),
+
+        // Error if the msg is not scoped to a board. execute handles those
+        // msgs itself, so they never run against a board's storage.
+        ExecuteMsg::RegisterName { .. } | ExecuteMsg::CreateBoard { .. } | ExecuteMsg::CloseBoard { .. } => {
+            Err(ContractError::NotScopedToBoard {})
+        }
     }
 }
 
 /// Adds the (address, score) pair to the smart contract iff the address if valid.
-pub fn try_add_address(deps: DepsMut, _info: MessageInfo, new_address: String, new_score: i32) -> Result<Response, ContractError> {
-    STATE.update(deps.storage, |mut state| -> Result<_, ContractError> {
-        // Deserialize the state HashMap from the JSON String.
-        let mut deserialized: HashMap<String, i32> = serde_json::from_str(&state.hash).unwrap();
-        
-        // Error if now_address is already in the HashMap.
-        if deserialized.contains_key(&new_address) {
+/// Only registrars may add any address. With open registration, anyone else
+/// may add their own address with the default score.
+pub fn try_add_address(deps: DepsMut, env: Env, info: MessageInfo, new_address: String, new_score: i32, memo: Option<String>) -> Result<Response, ContractError> {
+    // Error if someone other than a registrar is trying to add an
+    // address while registration is closed.
+    let config = CONFIG.load(deps.storage)?;
+    let is_registrar = has_role(deps.storage, Role::Registrar, &info.sender)?;
+    if !is_registrar && !config.open_registration {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Error if new_address is invalid.
+    let (new_address, kind) = validate_address(deps.api, &config, new_address)?;
+
+    // Error if someone other than a registrar is trying to register an
+    // address that is not their own, or is trying to pick their own score.
+    if !is_registrar {
+        if info.sender.as_str() != new_address {
             return Err(ContractError::Unauthorized {});
         }
+        if new_score != config.default_score {
+            return Err(ContractError::InvalidRegistrationScore { expected: config.default_score });
+        }
+    }
 
-        // insert the key value pair to the HashMap.
-        deserialized.insert(
-            new_address,
-            new_score,
-        );
+    // Error if new_address is already in the scores Map.
+    if SCORES.may_load(deps.storage, &new_address)?.is_some() {
+        return Err(ContractError::AddressAlreadyExists { address: new_address });
+    }
 
-        // Update the JSON String with the updated Hashmap.
-        state.hash = serde_json::to_string(&deserialized).unwrap().to_string();
-        Ok(state)
-    })?;
+    // Error if the score is out of bounds.
+    check_score(&config, None, new_score)?;
+
+    // Insert the key value pair to the scores Map.
+    append_history(deps.storage, &env, &info.sender, &new_address, None, Some(new_score), memo)?;
+    let diff = insert_score(deps.storage, &new_address, kind, new_score, env.block.height)?;
 
     // Return response.
-    Ok(Response::new().add_attribute("method", "add_address"))
+    Ok(Response::new()
+        .add_submessages(hook_msgs(deps.storage, &new_address, None, Some(new_score), diff)?)
+        .add_attribute("method", "add_address")
+        .add_attribute("address", new_address)
+        .add_attribute("score", new_score.to_string())
+    )
 }
 
 /// Updates the score at the given address iff the address is valid.
-pub fn try_set(deps: DepsMut, info: MessageInfo, address: String, new_score: i32) -> Result<Response, ContractError> {
-    STATE.update(deps.storage, |mut state| -> Result<_, ContractError> {
-        // Error if someone other than the owner is trying to set.
-        if info.sender != state.owner {
-            return Err(ContractError::Unauthorized {});
-        }
+pub fn try_set(deps: DepsMut, env: Env, info: MessageInfo, address: String, new_score: i32, memo: Option<String>) -> Result<Response, ContractError> {
+    // Error if someone other than a writer is trying to set.
+    assert_role(deps.storage, Role::Writer, &info.sender)?;
 
-        // Deserialize the state HashMap from the JSON String.
-        let mut deserialized: HashMap<String, i32> = serde_json::from_str(&state.hash).unwrap();
+    // Error if the address is not in the scores Map.
+    let (address, _) = normalize_address(deps.api, &address);
+    let old_score = match SCORES.may_load(deps.storage, &address)? {
+        Some(score) => score,
+        None => return Err(ContractError::AddressNotFound { address }),
+    };
 
-        // Error if the address is not in the HashMap.
-        if !deserialized.contains_key(&address) {
-            return Err(ContractError::Unauthorized {});
-        }
+    // Error if the new score is out of bounds or too far from the old one.
+    check_score(&CONFIG.load(deps.storage)?, Some(old_score), new_score)?;
 
-        // Update the score at the given address.
-        *deserialized.get_mut(&address).unwrap() = new_score;
+    // Update the score at the given address.
+    append_history(deps.storage, &env, &info.sender, &address, Some(old_score), Some(new_score), memo)?;
+    let diff = save_score(deps.storage, &address, new_score, env.block.height)?;
 
-        // Update the JSON String with the updated Hashmap.
-        state.hash = serde_json::to_string(&deserialized).unwrap().to_string();
-        Ok(state)
+    // Return response.
+    Ok(Response::new()
+        .add_submessages(hook_msgs(deps.storage, &address, Some(old_score), Some(new_score), diff)?)
+        .add_attribute("method", "set")
+        .add_attribute("address", address)
+        .add_attribute("score", new_score.to_string())
+    )
+}
+
+/// Adds (Add) or subtracts (Sub) delta from the score at the given address
+/// in one step, so concurrent writers cannot overwrite each other.
+#[allow(clippy::too_many_arguments)]
+pub fn try_apply_delta(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    operation: OverflowOperation,
+    delta: u32,
+    register_missing: bool,
+    memo: Option<String>,
+) -> Result<Response, ContractError> {
+    // Error if someone other than a writer is trying to update.
+    assert_role(deps.storage, Role::Writer, &info.sender)?;
+
+    // Load the current score, or register the address at the default
+    // score iff asked to and the sender is also a registrar.
+    let config = CONFIG.load(deps.storage)?;
+    let (key, kind) = normalize_address(deps.api, &address);
+    let (old_score, registered) = match SCORES.may_load(deps.storage, &key)? {
+        Some(score) => (score, false),
+        None if register_missing => {
+            assert_role(deps.storage, Role::Registrar, &info.sender)?;
+            validate_address(deps.api, &config, address)?;
+            (config.default_score, true)
+        }
+        None => return Err(ContractError::AddressNotFound { address: key }),
+    };
+
+    // Error if the new score does not fit in an i32.
+    let (method, new_score) = match operation {
+        OverflowOperation::Add => ("increment", old_score.checked_add_unsigned(delta)),
+        OverflowOperation::Sub => ("decrement", old_score.checked_sub_unsigned(delta)),
+        _ => return Err(ContractError::UnsupportedOperation { operation: operation.to_string() }),
+    };
+    let new_score = new_score.ok_or_else(|| ContractError::ScoreOverflow {
+        address: key.clone(),
+        source: OverflowError::new(operation, old_score, delta),
     })?;
 
+    // Error if the new score is out of bounds or too far from the old one.
+    check_score(&config, Some(old_score), new_score)?;
+
+    // Save the new score.
+    let old = if registered { None } else { Some(old_score) };
+    append_history(deps.storage, &env, &info.sender, &key, old, Some(new_score), memo)?;
+    let diff = if registered {
+        insert_score(deps.storage, &key, kind, new_score, env.block.height)?
+    } else {
+        save_score(deps.storage, &key, new_score, env.block.height)?
+    };
+
     // Return response.
-    Ok(Response::new().add_attribute("method", "set"))
+    Ok(Response::new()
+        .add_submessages(hook_msgs(deps.storage, &key, old, Some(new_score), diff)?)
+        .add_attribute("method", method)
+        .add_attribute("address", key)
+        .add_attribute("score", new_score.to_string())
+        .add_attribute("registered", registered.to_string())
+    )
 }
 
-// ======================================================================
-// Query Block
-// ======================================================================
+/// Removes the address and every record tied to it iff the address is stored.
+/// Only its history is kept, so the removal itself can be audited.
+pub fn try_remove_address(deps: DepsMut, env: Env, info: MessageInfo, address: String, memo: Option<String>) -> Result<Response, ContractError> {
+    // Error if someone other than a registrar is trying to remove.
+    assert_role(deps.storage, Role::Registrar, &info.sender)?;
 
-/// Calls a query that will not change the smart contract's contents.
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        // When the msg matches GetOwner, call query_owner.
-        QueryMsg::GetOwner {} => to_binary(&query_owner(deps)?),
+    // Error if the address is not in the scores Map.
+    let (address, _) = normalize_address(deps.api, &address);
+    let old_score = match SCORES.may_load(deps.storage, &address)? {
+        Some(score) => score,
+        None => return Err(ContractError::AddressNotFound { address }),
+    };
 
-        // When the msg matches GetHash, call query_hash.
-        QueryMsg::GetHash {} => to_binary(&query_hash(deps)?),
+    // Remove the score and everything indexed by it.
+    append_history(deps.storage, &env, &info.sender, &address, Some(old_score), None, memo)?;
+    let (score, diff) = remove_score(deps.storage, &address, env.block.height)?;
 
-        // When the msg matches GetScoreFromAddress,
-        // call query_query_score_from_addressowner.
-        QueryMsg::GetScoreFromAddress { address } => to_binary(&query_score_from_address(deps, address)?),
+    // Return response.
+    Ok(Response::new()
+        .add_submessages(hook_msgs(deps.storage, &address, Some(score), None, diff)?)
+        .add_attribute("method", "remove_address")
+        .add_event(Event::new("remove_address")
+            .add_attribute("address", address)
+            .add_attribute("score", score.to_string())
+        )
+    )
+}
+
+/// Replaces the profile of a stored key, or removes it if the profile is
+/// empty. Any address may set its own profile, and admins may set any.
+pub fn try_set_profile(deps: DepsMut, info: MessageInfo, address: Option<String>, profile: Profile) -> Result<Response, ContractError> {
+    // Error if someone other than the address itself or an admin is trying to set.
+    let address = match address {
+        Some(address) => normalize_address(deps.api, &address).0,
+        None => normalize_address(deps.api, info.sender.as_str()).0,
+    };
+    if address != normalize_address(deps.api, info.sender.as_str()).0 {
+        assert_role(deps.storage, Role::Admin, &info.sender)?;
+    }
+
+    // Error if the address is not in the scores Map or the profile is too large.
+    if SCORES.may_load(deps.storage, &address)?.is_none() {
+        return Err(ContractError::AddressNotFound { address });
+    }
+    validate_profile(&profile)?;
+
+    // Save the profile, or remove it if it is empty.
+    if profile == Profile::default() {
+        PROFILES.remove(deps.storage, &address);
+    } else {
+        PROFILES.save(deps.storage, &address, &profile)?;
     }
+
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "set_profile")
+        .add_attribute("address", address)
+    )
 }
 
-/// Return the owner of the provided smart contract.
-fn query_owner(deps: Deps) -> StdResult<OwnerResponse> {
-    // Load the state.
-    let state = STATE.load(deps.storage)?;
+/// Creates a new board, set up exactly like a new contract instantiated
+/// with msg. Only admins of the default board may create boards.
+pub fn try_create_board(deps: DepsMut, env: Env, info: MessageInfo, board: String, msg: InstantiateMsg) -> Result<Response, ContractError> {
+    // Error if someone other than an admin of the default board is trying to create.
+    assert_role(deps.storage, Role::Admin, &info.sender)?;
 
-    // Return a response containing the owner's name.
-    Ok(OwnerResponse { owner: state.owner })
+    // Error if the name is invalid or used by an open or closed board.
+    let board = normalize_name(&board)?;
+    if BOARDS.has(deps.storage, &board) {
+        return Err(ContractError::BoardAlreadyExists { board });
+    }
+
+    // Save the board.
+    let created = BoardInfo {
+        name: board.clone(),
+        created_by: info.sender.clone(),
+        created_height: env.block.height,
+        closed_height: None,
+    };
+    BOARDS.save(deps.storage, &board, &created)?;
+
+    // Set up the storage of the board.
+    let mut storage = PrefixedStorage::multilevel(deps.storage, &[BOARDS_NAMESPACE, board.as_bytes()]);
+    let board_deps = DepsMut { storage: &mut storage, api: deps.api, querier: deps.querier };
+    let (owner, count) = init_board(board_deps, &env, &info, msg)?;
+
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "create_board")
+        .add_attribute("board", board)
+        .add_attribute("count", count.to_string())
+        .add_attribute("owner", owner)
+    )
 }
 
-/// Return the HashMap of addresses and cooresponding
-/// scores converted to a JSON String that cooresponds
-/// to the provided smart contract.
-fn query_hash(deps: Deps) -> StdResult<HashResponse> {
-    // Load the state.
-    let state = STATE.load(deps.storage)?;
+/// Closes a board for good. A closed board keeps answering queries, but
+/// rejects every execute msg. Admins of the board or of the default board
+/// may close it.
+pub fn try_close_board(deps: DepsMut, env: Env, info: MessageInfo, board: String) -> Result<Response, ContractError> {
+    // Error if the board does not exist, is the default board, or is already closed.
+    let mut board = load_board(deps.storage, Some(&board))?;
+    if board.name == DEFAULT_BOARD {
+        return Err(ContractError::CannotCloseDefaultBoard {});
+    }
+    if board.closed_height.is_some() {
+        return Err(ContractError::BoardClosed { board: board.name });
+    }
+
+    // Error if the board is paused.
+    let board_storage = ReadonlyPrefixedStorage::multilevel(deps.storage, &[BOARDS_NAMESPACE, board.name.as_bytes()]);
+    assert_not_paused(&board_storage)?;
+
+    // Error if someone other than an admin of the board or the default board is trying to close.
+    if !has_role(&board_storage, Role::Admin, &info.sender)? && !has_role(deps.storage, Role::Admin, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Close the board.
+    board.closed_height = Some(env.block.height);
+    BOARDS.save(deps.storage, &board.name, &board)?;
 
-    // Return a response containing the state HashMap as a JSON String.
-    Ok(HashResponse { hash: state.hash })
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "close_board")
+        .add_attribute("board", board.name)
+    )
 }
 
-/// Return the acore the corresponds to the given address and smart contract.
-fn query_score_from_address(deps: Deps,  address: String) -> StdResult<ScoreFromAddressResponse> {
-    // Load the state.
-    let state = STATE.load(deps.storage)?;
+/// Claims a unique name for the sender. Any name the sender registered
+/// before is released, so each address has at most one name.
+pub fn try_register_name(deps: DepsMut, info: MessageInfo, name: String) -> Result<Response, ContractError> {
+    // Error if the name is invalid or belongs to someone else.
+    let name = normalize_name(&name)?;
+    match NAMES.may_load(deps.storage, &name)? {
+        Some(owner) if owner != info.sender => return Err(ContractError::NameTaken { name }),
+        _ => {}
+    }
 
-    // Deserialize the state HashMap.
-    let deserialized: HashMap<String, i32> = serde_json::from_str(&state.hash).unwrap();
+    // Release the previous name of the sender and save the new one.
+    if let Some(old_name) = NAME_OF.may_load(deps.storage, &info.sender)? {
+        NAMES.remove(deps.storage, &old_name);
+    }
+    NAMES.save(deps.storage, &name, &info.sender)?;
+    NAME_OF.save(deps.storage, &info.sender, &name)?;
 
-    // Get score at provided address.
-    let mut option = deserialized.get(&address);
-    let score: i32 = **option.get_or_insert(&(1 as i32));
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "register_name")
+        .add_attribute("name", name)
+        .add_attribute("address", info.sender)
+    )
+}
 
-    // Return a response containing the score at the provided address.
-    Ok(ScoreFromAddressResponse { score: score })
+/// Applies every operation in order through the matching handler. If any
+/// operation fails the whole message fails, so the transaction is reverted
+/// and none of the operations take effect. The attributes of operation i
+/// are reported with an "i." prefix, e.g. "0.method".
+pub fn try_batch_update(mut deps: DepsMut, env: Env, info: MessageInfo, updates: Vec<ScoreOp>) -> Result<Response, ContractError> {
+    // Error if the batch is larger than the configured maximum.
+    let max = CONFIG.load(deps.storage)?.max_batch_size;
+    if updates.len() > max as usize {
+        return Err(ContractError::BatchTooLarge { size: updates.len() as u64, max });
+    }
+
+    // Apply each operation and collect its results.
+    let mut response = Response::new()
+        .add_attribute("method", "batch_update")
+        .add_attribute("count", updates.len().to_string());
+    for (index, op) in updates.into_iter().enumerate() {
+        let result = match op {
+            ScoreOp::Set { address, new_score, memo } => try_set(deps.branch(), env.clone(), info.clone(), address, new_score, memo),
+            ScoreOp::Add { new_address, new_score, memo } => {
+                try_add_address(deps.branch(), env.clone(), info.clone(), new_address, new_score, memo)
+            }
+            ScoreOp::Remove { address, memo } => try_remove_address(deps.branch(), env.clone(), info.clone(), address, memo),
+            ScoreOp::Increment { address, delta, register_missing, memo } => {
+                try_apply_delta(deps.branch(), env.clone(), info.clone(), address, OverflowOperation::Add, delta, register_missing, memo)
+            }
+            ScoreOp::Decrement { address, delta, register_missing, memo } => {
+                try_apply_delta(deps.branch(), env.clone(), info.clone(), address, OverflowOperation::Sub, delta, register_missing, memo)
+            }
+        };
+        let result = result.map_err(|source| ContractError::BatchOperationFailed { index: index as u64, source: Box::new(source) })?;
+        response = response
+            .add_attributes(result.attributes.into_iter().map(|a| (format!("{}.{}", index, a.key), a.value)))
+            .add_submessages(result.messages)
+            .add_events(result.events);
+    }
+
+    // Return response.
+    Ok(response)
 }
 
-// ======================================================================
-// Testing Block
-// ======================================================================
+/// Updates the given config fields iff the sender is an admin
+/// and the resulting config is consistent.
+#[allow(clippy::too_many_arguments)]
+pub fn try_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    open_registration: Option<bool>,
+    default_score: Option<i32>,
+    max_batch_size: Option<u32>,
+    min_score: Option<i32>,
+    max_score: Option<i32>,
+    max_delta: Option<u32>,
+    max_history: Option<u32>,
+) -> Result<Response, ContractError> {
+    // Error if someone other than an admin is trying to update the config.
+    assert_role(deps.storage, Role::Admin, &info.sender)?;
 
-#[cfg(test)]
-mod tests {
-    // Imports for testing purposes.
-    use super::*;
-    use cosmwasm_std::testing::{MockApi, mock_dependencies, mock_env, mock_info, MockQuerier, MockStorage};
-    use cosmwasm_std::{coins, from_binary};
+    // Apply the given fields and error if the result is inconsistent.
+    let mut config = CONFIG.load(deps.storage)?;
+    config.open_registration = open_registration.unwrap_or(config.open_registration);
+    config.default_score = default_score.unwrap_or(config.default_score);
+    config.max_batch_size = max_batch_size.unwrap_or(config.max_batch_size);
+    config.min_score = min_score.unwrap_or(config.min_score);
+    config.max_score = max_score.unwrap_or(config.max_score);
+    config.max_delta = max_delta.unwrap_or(config.max_delta);
+    config.max_history = max_history.unwrap_or(config.max_history);
+    validate_config(&config)?;
+
+    // Save the config.
+    CONFIG.save(deps.storage, &config)?;
+
+    // Return response.
+    Ok(Response::new().add_attribute("method", "update_config"))
+}
+
+/// Adds a contract that is notified of the changes the hook kind covers.
+pub fn try_add_hook(deps: DepsMut, info: MessageInfo, addr: String, kind: HookKind) -> Result<Response, ContractError> {
+    // Error if someone other than an admin is trying to add a hook.
+    assert_role(deps.storage, Role::Admin, &info.sender)?;
+
+    // Error if the address is invalid or already a hook.
+    let addr = deps.api.addr_validate(&addr)?;
+    if HOOKS.has(deps.storage, (kind.as_str(), &addr)) {
+        return Err(ContractError::HookAlreadyRegistered { address: addr.into_string() });
+    }
+
+    // Add the hook.
+    HOOKS.save(deps.storage, (kind.as_str(), &addr), &Empty {})?;
+
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "add_hook")
+        .add_attribute("kind", kind.as_str())
+        .add_attribute("hook", addr)
+    )
+}
+
+/// Removes a contract added with AddHook.
+pub fn try_remove_hook(deps: DepsMut, info: MessageInfo, addr: String, kind: HookKind) -> Result<Response, ContractError> {
+    // Error if someone other than an admin is trying to remove a hook.
+    assert_role(deps.storage, Role::Admin, &info.sender)?;
+
+    // Error if the address is not a hook.
+    let addr = deps.api.addr_validate(&addr)?;
+    if !HOOKS.has(deps.storage, (kind.as_str(), &addr)) {
+        return Err(ContractError::HookNotRegistered { address: addr.into_string() });
+    }
+
+    // Remove the hook.
+    HOOKS.remove(deps.storage, (kind.as_str(), &addr));
+
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "remove_hook")
+        .add_attribute("kind", kind.as_str())
+        .add_attribute("hook", addr)
+    )
+}
+
+/// Pauses every message except Unpause and the ownership transfer messages.
+pub fn try_pause(deps: DepsMut, env: Env, info: MessageInfo, reason: String) -> Result<Response, ContractError> {
+    // Error if someone other than an admin is trying to pause.
+    assert_role(deps.storage, Role::Admin, &info.sender)?;
+
+    // Save who paused the contract, when and why.
+    let pause = PauseInfo {
+        paused_by: info.sender,
+        height: env.block.height,
+        time: env.block.time,
+        reason,
+    };
+    PAUSE.save(deps.storage, &pause)?;
+
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "pause")
+        .add_attribute("paused_by", pause.paused_by)
+        .add_attribute("reason", pause.reason)
+    )
+}
+
+/// Lifts a pause.
+pub fn try_unpause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    // Error if someone other than an admin is trying to unpause.
+    assert_role(deps.storage, Role::Admin, &info.sender)?;
+
+    // Error if the contract is not paused.
+    if PAUSE.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NotPaused {});
+    }
+
+    // Lift the pause.
+    PAUSE.remove(deps.storage);
+
+    // Return response.
+    Ok(Response::new().add_attribute("method", "unpause"))
+}
+
+/// Proposes a new owner. Ownership does not move until the
+/// new owner accepts it, so a typo cannot lock the contract.
+pub fn try_propose_owner(deps: DepsMut, env: Env, info: MessageInfo, new_owner: String, expiry: Option<Expiration>) -> Result<Response, ContractError> {
+    // Error if someone other than the owner is trying to propose.
+    let state = STATE.load(deps.storage)?;
+    if !state.is_owner(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Error if the new owner is invalid or the proposal is already expired.
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+    let expiry = expiry.unwrap_or(Expiration::Never {});
+    if expiry.is_expired(&env.block) {
+        return Err(ContractError::OwnershipProposalExpired {});
+    }
+
+    // Save the proposal, replacing any earlier one.
+    let pending = PendingOwner { new_owner, expiry };
+    PENDING_OWNER.save(deps.storage, &pending)?;
+
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "propose_owner")
+        .add_attribute("new_owner", pending.new_owner)
+        .add_attribute("expiry", pending.expiry.to_string())
+    )
+}
+
+/// Makes the sender the owner iff they are the proposed owner
+/// and the proposal has not expired.
+pub fn try_accept_ownership(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    // Error if there is no proposal or the sender is not the proposed owner.
+    let pending = PENDING_OWNER.may_load(deps.storage)?.ok_or(ContractError::NoPendingOwner {})?;
+    if info.sender != pending.new_owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Error if the proposal has expired.
+    if pending.expiry.is_expired(&env.block) {
+        return Err(ContractError::OwnershipProposalExpired {});
+    }
+
+    // Move ownership and clear the proposal.
+    STATE.update(deps.storage, |mut state| -> StdResult<_> {
+        state.owner = Some(info.sender.clone());
+        Ok(state)
+    })?;
+    PENDING_OWNER.remove(deps.storage);
+
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "accept_ownership")
+        .add_attribute("owner", info.sender)
+    )
+}
+
+/// Withdraws the pending ownership proposal.
+pub fn try_cancel_ownership_proposal(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    // Error if someone other than the owner is trying to cancel.
+    let state = STATE.load(deps.storage)?;
+    if !state.is_owner(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Error if there is no proposal to cancel.
+    if PENDING_OWNER.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingOwner {});
+    }
+    PENDING_OWNER.remove(deps.storage);
+
+    // Return response.
+    Ok(Response::new().add_attribute("method", "cancel_ownership_proposal"))
+}
+
+/// Leaves the contract without an owner and clears any pending proposal.
+pub fn try_renounce_ownership(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    // Error if someone other than the owner is trying to renounce.
+    let mut state = STATE.load(deps.storage)?;
+    if !state.is_owner(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Clear the owner and any pending proposal.
+    state.owner = None;
+    STATE.save(deps.storage, &state)?;
+    PENDING_OWNER.remove(deps.storage);
+
+    // Return response.
+    Ok(Response::new().add_attribute("method", "renounce_ownership"))
+}
+
+/// Grants the role to the given address.
+pub fn try_grant_role(deps: DepsMut, info: MessageInfo, role: Role, address: String) -> Result<Response, ContractError> {
+    // Error if the sender may not manage the role.
+    assert_can_manage_role(deps.storage, role, &info.sender)?;
+
+    // Error if the address is invalid or already holds the role.
+    let address = deps.api.addr_validate(&address)?;
+    if ROLES.has(deps.storage, (role.as_str(), &address)) {
+        return Err(ContractError::RoleAlreadyGranted { role: role.as_str().to_string(), address: address.into_string() });
+    }
+
+    // Grant the role.
+    ROLES.save(deps.storage, (role.as_str(), &address), &Empty {})?;
+
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "grant_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address)
+    )
+}
+
+/// Revokes the role from the given address.
+pub fn try_revoke_role(deps: DepsMut, info: MessageInfo, role: Role, address: String) -> Result<Response, ContractError> {
+    // Error if the sender may not manage the role.
+    assert_can_manage_role(deps.storage, role, &info.sender)?;
+
+    // Error if the address does not hold the role.
+    let address = deps.api.addr_validate(&address)?;
+    if !ROLES.has(deps.storage, (role.as_str(), &address)) {
+        return Err(ContractError::RoleNotGranted { role: role.as_str().to_string(), address: address.into_string() });
+    }
+
+    // Revoke the role.
+    ROLES.remove(deps.storage, (role.as_str(), &address));
+
+    // Return response.
+    Ok(Response::new()
+        .add_attribute("method", "revoke_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address)
+    )
+}
+
+/// Returns the normalized key and kind of an address that is about to be
+/// stored, iff the address is valid or the config allows external keys.
+fn validate_address(api: &dyn Api, config: &Config, address: String) -> Result<(String, KeyKind), ContractError> {
+    // Error if the address is empty or only whitespace.
+    if address.trim().is_empty() {
+        return Err(ContractError::InvalidAddress { address });
+    }
+
+    // Error if the address is not valid and external keys are not allowed.
+    let (key, kind) = normalize_address(api, &address);
+    if kind == KeyKind::External && !config.allow_external_keys {
+        return Err(ContractError::InvalidAddress { address });
+    }
+
+    Ok((key, kind))
+}
+
+/// Returns an error if the bounds of the config are inconsistent.
+fn validate_config(config: &Config) -> Result<(), ContractError> {
+    // Error if the bounds are inverted.
+    if config.min_score > config.max_score {
+        return Err(ContractError::InvalidConfig { reason: format!("min_score {} is above max_score {}", config.min_score, config.max_score) });
+    }
+
+    // Error if unknown addresses would report a score no write could store.
+    if config.default_score < config.min_score || config.default_score > config.max_score {
+        return Err(ContractError::InvalidConfig { reason: format!("default_score {} is outside the score bounds", config.default_score) });
+    }
+
+    Ok(())
+}
+
+/// Returns an error if new_score is outside the bounds of the config, or if
+/// it moves further from old_score than the config allows. Every handler
+/// that writes a score must call this before writing it.
+fn check_score(config: &Config, old_score: Option<i32>, new_score: i32) -> Result<(), ContractError> {
+    // Error if the new score is out of bounds.
+    if new_score < config.min_score || new_score > config.max_score {
+        return Err(ContractError::ScoreOutOfRange { min: config.min_score, max: config.max_score });
+    }
+
+    // Error if the change is too large.
+    if let Some(old_score) = old_score {
+        let delta = (i64::from(new_score) - i64::from(old_score)).unsigned_abs() as u32;
+        if delta > config.max_delta {
+            return Err(ContractError::DeltaTooLarge { delta, max: config.max_delta });
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns an error if any field of the profile is above its size limit.
+fn validate_profile(profile: &Profile) -> Result<(), ContractError> {
+    // Error if a field is too long.
+    let mut fields = vec![];
+    if let Some(display_name) = &profile.display_name {
+        fields.push(("display_name".to_string(), display_name, MAX_DISPLAY_NAME_LENGTH));
+    }
+    if let Some(uri) = &profile.uri {
+        fields.push(("uri".to_string(), uri, MAX_URI_LENGTH));
+    }
+    for (key, value) in profile.tags.iter() {
+        fields.push(("tag key".to_string(), key, MAX_TAG_KEY_LENGTH));
+        fields.push((format!("tag {}", key), value, MAX_TAG_VALUE_LENGTH));
+    }
+    for (field, value, max) in fields {
+        if value.len() > max as usize {
+            return Err(ContractError::ProfileFieldTooLong { field, length: value.len() as u64, max });
+        }
+    }
+
+    // Error if there are too many tags or a key appears twice.
+    if profile.tags.len() > MAX_PROFILE_TAGS as usize {
+        return Err(ContractError::TooManyProfileTags { count: profile.tags.len() as u64, max: MAX_PROFILE_TAGS });
+    }
+    let mut keys = BTreeSet::new();
+    for (key, _) in profile.tags.iter() {
+        if !keys.insert(key) {
+            return Err(ContractError::DuplicateProfileTag { key: key.clone() });
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the normalized form of a name: trimmed and lowercased. Names
+/// are 3 to 32 bytes long, start with a letter, and otherwise only use
+/// letters, digits, '-' and '_'.
+fn normalize_name(name: &str) -> Result<String, ContractError> {
+    let normalized = name.trim().to_lowercase();
+    let invalid = |reason: &str| ContractError::InvalidName { name: name.to_string(), reason: reason.to_string() };
+
+    // Error if the name is too short or too long.
+    if normalized.len() < MIN_NAME_LENGTH || normalized.len() > MAX_NAME_LENGTH {
+        return Err(invalid(&format!("must be {} to {} bytes long", MIN_NAME_LENGTH, MAX_NAME_LENGTH)));
+    }
+
+    // Error if the name uses anything other than the allowed characters.
+    if !normalized.starts_with(|c: char| c.is_ascii_lowercase()) {
+        return Err(invalid("must start with a letter"));
+    }
+    if !normalized.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_') {
+        return Err(invalid("may only contain letters, digits, '-' and '_'"));
+    }
+
+    Ok(normalized)
+}
+
+/// Returns an error if the board behind storage is paused.
+fn assert_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
+    match PAUSE.may_load(storage)? {
+        Some(pause) => Err(ContractError::ContractPaused { reason: pause.reason }),
+        None => Ok(()),
+    }
+}
+
+/// Returns the board with the given name, or the default board if None.
+fn load_board(storage: &dyn Storage, board: Option<&str>) -> Result<BoardInfo, ContractError> {
+    let board = match board {
+        Some(board) => normalize_name(board)?,
+        None => DEFAULT_BOARD.to_string(),
+    };
+    match BOARDS.may_load(storage, &board)? {
+        Some(info) => Ok(info),
+        None => Err(ContractError::BoardNotFound { board }),
+    }
+}
+
+/// Returns the key a score query refers to. Queries take exactly one of an
+/// address, which is normalized, or a registered name, which resolves to
+/// the address that registered it. An address is never read as a name.
+fn resolve_key(api: &dyn Api, names: &dyn Storage, address: Option<String>, name: Option<String>) -> StdResult<String> {
+    match (address, name) {
+        (Some(address), None) => Ok(normalize_address(api, &address).0),
+        (None, Some(name)) => {
+            let name = normalize_name(&name).map_err(|err| StdError::generic_err(err.to_string()))?;
+            match NAMES.may_load(names, &name)? {
+                Some(owner) => Ok(owner.into_string()),
+                None => Err(StdError::generic_err(format!("Name not found: {}", name))),
+            }
+        }
+        _ => Err(StdError::generic_err("Exactly one of address and name must be set")),
+    }
+}
+
+/// Returns the key an address is stored under and its kind. Addresses are
+/// lowercased and validated through the Api, so "Terra1ABC" and "terra1abc"
+/// share a key. Anything that fails validation is kept verbatim.
+fn normalize_address(api: &dyn Api, address: &str) -> (String, KeyKind) {
+    match api.addr_validate(&address.to_lowercase()) {
+        Ok(addr) => (addr.into_string(), KeyKind::Address),
+        Err(_) => (address.to_string(), KeyKind::External),
+    }
+}
+
+// ======================================================================
+// Query Block
+// ======================================================================
+
+/// Calls a query that will not change the smart contract's contents.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    // Scope the storage to the board of the msg. The board list and the
+    // name registry are read from the top level, through root.
+    let board = load_board(deps.storage, msg.board()).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let prefixed;
+    let storage: &dyn Storage = if board.name == DEFAULT_BOARD {
+        deps.storage
+    } else {
+        prefixed = ReadonlyPrefixedStorage::multilevel(deps.storage, &[BOARDS_NAMESPACE, board.name.as_bytes()]);
+        &prefixed
+    };
+    let root = deps;
+    let deps = Deps { storage, api: root.api, querier: root.querier };
+
+    match msg {
+        // When the msg matches GetOwner, call query_owner.
+        QueryMsg::GetOwner { .. } => to_binary(&query_owner(deps)?),
+
+        // When the msg matches PendingOwner, call query_pending_owner.
+        QueryMsg::PendingOwner { .. } => to_binary(&query_pending_owner(deps)?),
+
+        // When the msg matches PauseStatus, call query_pause_status.
+        QueryMsg::PauseStatus { .. } => to_binary(&query_pause_status(deps)?),
+
+        // When the msg matches ListRoleMembers, call query_list_role_members.
+        QueryMsg::ListRoleMembers { role, start_after, limit, .. } => to_binary(&query_list_role_members(deps, role, start_after, limit)?),
+
+        // When the msg matches HasRole, call query_has_role.
+        QueryMsg::HasRole { role, address, .. } => to_binary(&query_has_role(deps, role, address)?),
+
+        // When the msg matches GetConfig, call query_config.
+        QueryMsg::GetConfig { .. } => to_binary(&query_config(deps)?),
+
+        // When the msg matches GetHash, call query_hash.
+        QueryMsg::GetHash { .. } => to_binary(&query_hash(deps)?),
+
+        // When the msg matches StateDigest, call query_state_digest.
+        QueryMsg::StateDigest { .. } => to_binary(&query_state_digest(deps)?),
+
+        // When the msg matches GetScoreFromAddress,
+        // call query_query_score_from_addressowner.
+        QueryMsg::GetScoreFromAddress { address, name, include_profile, .. } => {
+            to_binary(&query_score_from_address(deps, root.storage, address, name, include_profile)?)
+        }
+
+        // When the msg matches ListScores, call query_list_scores.
+        QueryMsg::ListScores { start_after, limit, include_profile, .. } => {
+            to_binary(&query_list_scores(deps, start_after, limit, include_profile)?)
+        }
+
+        // When the msg matches TopScores, call query_leaderboard from the top.
+        QueryMsg::TopScores { start_after, limit, include_profile, .. } => {
+            to_binary(&query_leaderboard(deps, start_after, limit, include_profile, Order::Descending)?)
+        }
+
+        // When the msg matches BottomScores, call query_leaderboard from the bottom.
+        QueryMsg::BottomScores { start_after, limit, include_profile, .. } => {
+            to_binary(&query_leaderboard(deps, start_after, limit, include_profile, Order::Ascending)?)
+        }
+
+        // When the msg matches Rank, call query_rank.
+        QueryMsg::Rank { address, name, .. } => to_binary(&query_rank(deps, root.storage, address, name)?),
+
+        // When the msg matches ScoreAtHeight, call query_score_at_height.
+        QueryMsg::ScoreAtHeight { address, name, height, .. } => {
+            to_binary(&query_score_at_height(deps, root.storage, address, name, height)?)
+        }
+
+        // When the msg matches TotalAtHeight, call query_total_at_height.
+        QueryMsg::TotalAtHeight { height, .. } => to_binary(&query_total_at_height(deps, height)?),
+
+        // When the msg matches Admin, call query_admin.
+        QueryMsg::Admin { .. } => to_binary(&query_admin(deps)?),
+
+        // When the msg matches TotalWeight, call query_total_weight.
+        QueryMsg::TotalWeight { .. } => to_binary(&query_total_weight(deps)?),
+
+        // When the msg matches ListMembers, call query_list_members.
+        QueryMsg::ListMembers { start_after, limit, .. } => to_binary(&query_list_members(deps, start_after, limit)?),
+
+        // When the msg matches Member, call query_member.
+        QueryMsg::Member { addr, at_height, .. } => to_binary(&query_member(deps, addr, at_height)?),
+
+        // When the msg matches Hooks, call query_hooks.
+        QueryMsg::Hooks { .. } => to_binary(&query_hooks(deps)?),
+
+        // When the msg matches ListHooks, call query_list_hooks.
+        QueryMsg::ListHooks { .. } => to_binary(&query_list_hooks(deps)?),
+
+        // When the msg matches ScoreHistory, call query_score_history.
+        QueryMsg::ScoreHistory { address, name, start_after, limit, .. } => {
+            to_binary(&query_score_history(deps, root.storage, address, name, start_after, limit)?)
+        }
+
+        // When the msg matches ListBoards, call query_list_boards.
+        QueryMsg::ListBoards { start_after, limit } => to_binary(&query_list_boards(root, start_after, limit)?),
+
+        // When the msg matches ResolveName, call query_resolve_name.
+        QueryMsg::ResolveName { name } => to_binary(&query_resolve_name(root, name)?),
+
+        // When the msg matches ReverseLookup, call query_reverse_lookup.
+        QueryMsg::ReverseLookup { address } => to_binary(&query_reverse_lookup(root, address)?),
+    }
+}
+
+/// Return the owner of the provided smart contract.
+fn query_owner(deps: Deps) -> StdResult<OwnerResponse> {
+    // Load the state.
+    let state = STATE.load(deps.storage)?;
+
+    // Return a response containing the owner's name.
+    Ok(OwnerResponse { owner: state.owner })
+}
+
+/// Return the ownership transfer waiting to be accepted, if any.
+fn query_pending_owner(deps: Deps) -> StdResult<PendingOwnerResponse> {
+    // Load the proposal.
+    let pending = PENDING_OWNER.may_load(deps.storage)?;
+
+    // Return a response containing the proposed owner and expiry.
+    Ok(PendingOwnerResponse {
+        new_owner: pending.as_ref().map(|pending| pending.new_owner.clone()),
+        expiry: pending.map(|pending| pending.expiry),
+    })
+}
+
+/// Returns whether the contract is paused, and if so by whom, when and why.
+fn query_pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+    let pause = PAUSE.may_load(deps.storage)?;
+    Ok(PauseStatusResponse { paused: pause.is_some(), pause })
+}
+
+/// Return one page of the addresses the role was granted to, ordered by address.
+fn query_list_role_members(deps: Deps, role: Role, start_after: Option<String>, limit: Option<u32>) -> StdResult<RoleMembersResponse> {
+    // Clamp the page size and start right after the given address.
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    // Collect the page from the roles Map.
+    let members = ROLES
+        .prefix(role.as_str())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|address| Ok(Addr::unchecked(String::from_utf8(address)?)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Return a response containing the page.
+    Ok(RoleMembersResponse { members })
+}
+
+/// Return whether the given address holds the role.
+fn query_has_role(deps: Deps, role: Role, address: String) -> StdResult<HasRoleResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let has_role = has_role(deps.storage, role, &address)?;
+    Ok(HasRoleResponse { has_role })
+}
+
+/// Return the settings of the smart contract.
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        allow_external_keys: config.allow_external_keys,
+        open_registration: config.open_registration,
+        default_score: config.default_score,
+        max_batch_size: config.max_batch_size,
+        min_score: config.min_score,
+        max_score: config.max_score,
+        max_delta: config.max_delta,
+        max_history: config.max_history,
+    })
+}
+
+/// Return the map of addresses and cooresponding
+/// scores converted to a JSON String that cooresponds
+/// to the provided smart contract.
+fn query_hash(deps: Deps) -> StdResult<HashResponse> {
+    // Collect every (address, score) pair from the scores Map. A BTreeMap
+    // keeps the addresses sorted, so every node returns the same String.
+    let hash = SCORES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (address, score) = item?;
+            Ok((String::from_utf8(address)?, score))
+        })
+        .collect::<StdResult<BTreeMap<String, i32>>>()?;
+
+    // Convert the BTreeMap to a JSON String.
+    let hash = serde_json::to_string(&hash)
+        .map_err(|err| StdError::serialize_err("BTreeMap<String, i32>", err))?;
+
+    // Return a response containing the BTreeMap as a JSON String.
+    Ok(HashResponse { hash })
+}
+
+/// Return the root hash of the digest trie over every (address, score) pair.
+fn query_state_digest(deps: Deps) -> StdResult<StateDigestResponse> {
+    let digest = load_digest(deps.storage)?;
+    let digest = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    Ok(StateDigestResponse { digest })
+}
+
+/// Return the acore the corresponds to the given address and smart contract.
+fn query_score_from_address(
+    deps: Deps,
+    names: &dyn Storage,
+    address: Option<String>,
+    name: Option<String>,
+    include_profile: bool,
+) -> StdResult<ScoreFromAddressResponse> {
+    // Get score and key kind at provided address or name.
+    let address = resolve_key(deps.api, names, address, name)?;
+    let score = SCORES.may_load(deps.storage, &address)?;
+    let kind = KEY_KINDS.may_load(deps.storage, &address)?;
+
+    // Fall back to the default score if the address is not stored.
+    let exists = score.is_some();
+    let score = match score {
+        Some(score) => score,
+        None => CONFIG.load(deps.storage)?.default_score,
+    };
+
+    // Load the profile if it was requested.
+    let profile = load_profile(deps.storage, &address, include_profile)?;
+
+    // Return a response containing the score at the provided address.
+    Ok(ScoreFromAddressResponse { address, score, exists, kind, profile })
+}
+
+/// Return one page of (address, score) entries ordered by address.
+fn query_list_scores(deps: Deps, start_after: Option<String>, limit: Option<u32>, include_profile: bool) -> StdResult<ListScoresResponse> {
+    // Clamp the page size and start right after the given address.
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|address| Bound::exclusive(normalize_address(deps.api, &address).0));
+
+    // Collect the page from the scores Map.
+    let scores = SCORES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (address, score) = item?;
+            let address = String::from_utf8(address)?;
+            let kind = KEY_KINDS.load(deps.storage, &address)?;
+            let profile = load_profile(deps.storage, &address, include_profile)?;
+            Ok(ScoreEntry { address, score, kind, profile })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Return a response containing the page.
+    Ok(ListScoresResponse { scores })
+}
+
+/// Return one page of (address, score) entries ordered by score. Descending
+/// starts from the highest score and Ascending from the lowest.
+fn query_leaderboard(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    include_profile: bool,
+    order: Order,
+) -> StdResult<ListScoresResponse> {
+    // Clamp the page size and start right after the given address.
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match start_after {
+        Some(address) => Some(Bound::exclusive(load_rank_key(deps.storage, &normalize_address(deps.api, &address).0)?)),
+        None => None,
+    };
+    let (min, max) = match order {
+        Order::Ascending => (start, None),
+        Order::Descending => (None, start),
+    };
+
+    // Collect the page from the leaderboard index.
+    let scores = LEADERBOARD
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|item| {
+            let (_, address) = item?;
+            let score = SCORES.load(deps.storage, &address)?;
+            let kind = KEY_KINDS.load(deps.storage, &address)?;
+            let profile = load_profile(deps.storage, &address, include_profile)?;
+            Ok(ScoreEntry { address, score, kind, profile })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Return a response containing the page.
+    Ok(ListScoresResponse { scores })
+}
+
+/// Return the leaderboard position of the given address, where 1 is the
+/// highest score. Its cost does not depend on how many addresses are stored.
+fn query_rank(deps: Deps, names: &dyn Storage, address: Option<String>, name: Option<String>) -> StdResult<RankResponse> {
+    // Load the score and the leaderboard key of the address or name.
+    let address = resolve_key(deps.api, names, address, name)?;
+    let score = SCORES.load(deps.storage, &address)?;
+    let key = load_rank_key(deps.storage, &address)?;
+
+    // Count every entry ranked above the address.
+    let above = count_above(deps.storage, &key)?;
+
+    // Return a response containing the rank.
+    Ok(RankResponse { address, score, rank: above + 1 })
+}
+
+/// Returns the score of an address at the start of the given block.
+fn query_score_at_height(
+    deps: Deps,
+    names: &dyn Storage,
+    address: Option<String>,
+    name: Option<String>,
+    height: u64,
+) -> StdResult<ScoreAtHeightResponse> {
+    let address = resolve_key(deps.api, names, address, name)?;
+    let score = SCORES.may_load_at_height(deps.storage, &address, height)?;
+    Ok(ScoreAtHeightResponse { address, height, score })
+}
+
+/// Returns the sum of every score at the start of the given block.
+fn query_total_at_height(deps: Deps, height: u64) -> StdResult<TotalAtHeightResponse> {
+    let total = TOTAL.may_load_at_height(deps.storage, TOTAL_KEY, height)?.unwrap_or_default();
+    Ok(TotalAtHeightResponse { height, total })
+}
+
+/// Returns the cw4 admin, which is the owner.
+fn query_admin(deps: Deps) -> StdResult<AdminResponse> {
+    let state = STATE.load(deps.storage)?;
+    Ok(AdminResponse { admin: state.owner.map(Addr::into_string) })
+}
+
+/// Returns the sum of every member weight.
+fn query_total_weight(deps: Deps) -> StdResult<TotalWeightResponse> {
+    let weight = TOTAL_WEIGHT.may_load(deps.storage)?.unwrap_or_default();
+    Ok(TotalWeightResponse { weight })
+}
+
+/// Returns one page of members ordered by address.
+fn query_list_members(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<MemberListResponse> {
+    // Clamp the page size and start right after the given address.
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|address| Bound::exclusive(normalize_address(deps.api, &address).0));
+
+    // Collect the page from the members SnapshotMap.
+    let members = MEMBERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (addr, weight) = item?;
+            Ok(Member { addr: String::from_utf8(addr)?, weight })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Return a response containing the page.
+    Ok(MemberListResponse { members })
+}
+
+/// Returns the weight of a member, now or at the start of the given block.
+fn query_member(deps: Deps, addr: String, at_height: Option<u64>) -> StdResult<MemberResponse> {
+    let (addr, _) = normalize_address(deps.api, &addr);
+    let weight = match at_height {
+        Some(height) => MEMBERS.may_load_at_height(deps.storage, &addr, height)?,
+        None => MEMBERS.may_load(deps.storage, &addr)?,
+    };
+    Ok(MemberResponse { weight })
+}
+
+/// Returns the Member hooks, ordered by address.
+fn query_hooks(deps: Deps) -> StdResult<HooksResponse> {
+    let hooks = load_hooks(deps.storage, HookKind::Member)?
+        .into_iter()
+        .map(Addr::into_string)
+        .collect();
+    Ok(HooksResponse { hooks })
+}
+
+/// Returns every hook, ordered by kind then address.
+fn query_list_hooks(deps: Deps) -> StdResult<ListHooksResponse> {
+    let mut hooks = vec![];
+    for kind in [HookKind::Member, HookKind::Score].iter() {
+        for addr in load_hooks(deps.storage, *kind)? {
+            hooks.push(HookEntry { addr, kind: *kind });
+        }
+    }
+    Ok(ListHooksResponse { hooks })
+}
+
+/// Returns one page of the history of an address, oldest record first.
+fn query_score_history(
+    deps: Deps,
+    names: &dyn Storage,
+    address: Option<String>,
+    name: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ScoreHistoryResponse> {
+    // Clamp the page size and start right after the given id.
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    // Collect the page from the history Map.
+    let address = resolve_key(deps.api, names, address, name)?;
+    let records = HISTORY
+        .prefix(&address)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, record)| record))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Return a response containing the page.
+    Ok(ScoreHistoryResponse { address, records })
+}
+
+/// Returns one page of boards ordered by name.
+fn query_list_boards(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<ListBoardsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let boards = BOARDS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, board)| board))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ListBoardsResponse { boards })
+}
+
+/// Returns the address that registered the given name, if any.
+fn query_resolve_name(deps: Deps, name: String) -> StdResult<ResolveNameResponse> {
+    let name = normalize_name(&name).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let address = NAMES.may_load(deps.storage, &name)?;
+    Ok(ResolveNameResponse { name, address })
+}
+
+/// Returns the name the given address registered, if any.
+fn query_reverse_lookup(deps: Deps, address: String) -> StdResult<ReverseLookupResponse> {
+    let addr = deps.api.addr_validate(&address.to_lowercase())?;
+    let name = NAME_OF.may_load(deps.storage, &addr)?;
+    Ok(ReverseLookupResponse { address: addr.into_string(), name })
+}
+
+// ======================================================================
+// Permission Helper Block
+// ======================================================================
+
+/// Returns true iff the address holds the role. The owner holds every
+/// role, and admins hold every role except Admin.
+fn has_role(storage: &dyn Storage, role: Role, address: &Addr) -> StdResult<bool> {
+    if STATE.load(storage)?.is_owner(address) || ROLES.has(storage, (role.as_str(), address)) {
+        return Ok(true);
+    }
+    Ok(role != Role::Admin && ROLES.has(storage, (Role::Admin.as_str(), address)))
+}
+
+/// Errors iff the address does not hold the role.
+fn assert_role(storage: &dyn Storage, role: Role, address: &Addr) -> Result<(), ContractError> {
+    if !has_role(storage, role, address)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// Errors iff the address may not grant or revoke the role. Only the
+/// owner manages admins, so admins cannot lock each other out.
+fn assert_can_manage_role(storage: &dyn Storage, role: Role, address: &Addr) -> Result<(), ContractError> {
+    let allowed = match role {
+        Role::Admin => STATE.load(storage)?.is_owner(address),
+        Role::Writer | Role::Registrar => has_role(storage, Role::Admin, address)?,
+    };
+    if !allowed {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+// ======================================================================
+// Storage Helper Block
+// ======================================================================
+
+/// Saves the score and key kind of a new address.
+/// Returns the change to its member weight, if any.
+fn insert_score(storage: &mut dyn Storage, address: &str, kind: KeyKind, score: i32, height: u64) -> StdResult<Option<MemberDiff>> {
+    KEY_KINDS.save(storage, address, &kind)?;
+    save_score(storage, address, score, height)
+}
+
+/// Saves the score at the given address as of the given block height and
+/// keeps the leaderboard index, total and member weight in sync. Every write
+/// to the scores Map must go through this function or remove_score.
+/// Returns the change to the member weight, if any.
+fn save_score(storage: &mut dyn Storage, address: &str, score: i32, height: u64) -> StdResult<Option<MemberDiff>> {
+    let (seq, old_score) = match ENTRY_SEQ.may_load(storage, address)? {
+        // Drop the old leaderboard entry of a known address.
+        Some(seq) => {
+            let old_score = SCORES.load(storage, address)?;
+            LEADERBOARD.remove(storage, &rank_key(old_score, seq));
+            update_rank_counts(storage, &rank_key(old_score, seq), false)?;
+            (seq, old_score)
+        }
+
+        // Hand out the next sequence number to a new address.
+        None => {
+            let seq = NEXT_SEQ.may_load(storage)?.unwrap_or_default();
+            NEXT_SEQ.save(storage, &(seq + 1))?;
+            ENTRY_SEQ.save(storage, address, &seq)?;
+            (seq, 0)
+        }
+    };
+
+    // Save the score, its leaderboard entry and the new total. Adding the
+    // digest leaf replaces the old one of a known address.
+    SCORES.save(storage, address, &score, height)?;
+    update_digest(storage, address, score, true)?;
+    update_total(storage, i64::from(score) - i64::from(old_score), height)?;
+    LEADERBOARD.save(storage, &rank_key(score, seq), &address.to_string())?;
+    update_rank_counts(storage, &rank_key(score, seq), true)?;
+    update_member(storage, address, Some(score), height)
+}
+
+/// Adds (added) or removes (!added) one entry from the count of every
+/// proper prefix of its rank_key. Counts that drop to 0 are removed.
+fn update_rank_counts(storage: &mut dyn Storage, key: &[u8], added: bool) -> StdResult<()> {
+    for len in 1..key.len() {
+        let count_key = rank_count_key(&key[..len]);
+        let count = RANK_COUNTS.may_load(storage, &count_key)?.unwrap_or_default();
+        match (added, count) {
+            (true, count) => RANK_COUNTS.save(storage, &count_key, &(count + 1))?,
+            (false, 0) | (false, 1) => RANK_COUNTS.remove(storage, &count_key),
+            (false, count) => RANK_COUNTS.save(storage, &count_key, &(count - 1))?,
+        }
+    }
+    Ok(())
+}
+
+/// Returns the number of leaderboard entries that sort above the given
+/// rank_key. For every byte of the key, it adds up the entries that share
+/// the bytes before it and have a higher byte there. That reads at most
+/// 255 counts per byte, and 255 leaderboard keys for the last byte.
+fn count_above(storage: &dyn Storage, key: &[u8]) -> StdResult<u64> {
+    let mut above = 0;
+    for len in 1..=key.len() {
+        // Skip a byte that no entry can be higher at.
+        let (parent, byte) = (&key[..len - 1], key[len - 1]);
+        if byte == u8::MAX {
+            continue;
+        }
+        let min = [parent, &[byte + 1]].concat();
+        let max = [parent, &[u8::MAX]].concat();
+
+        // Read the counts of the higher prefixes, or the higher entries themselves.
+        above += if len < key.len() {
+            RANK_COUNTS
+                .range(
+                    storage,
+                    Some(Bound::inclusive(rank_count_key(&min))),
+                    Some(Bound::inclusive(rank_count_key(&max))),
+                    Order::Ascending,
+                )
+                .map(|item| item.map(|(_, count)| count))
+                .sum::<StdResult<u64>>()?
+        } else {
+            LEADERBOARD
+                .keys(storage, Some(Bound::inclusive(min)), Some(Bound::inclusive(max)), Order::Ascending)
+                .count() as u64
+        };
+    }
+    Ok(above)
+}
+
+/// Removes the score at the given address as of the given block height,
+/// along with its key kind, leaderboard entry and member weight.
+/// Returns the removed score and the change to the member weight, if any.
+fn remove_score(storage: &mut dyn Storage, address: &str, height: u64) -> StdResult<(i32, Option<MemberDiff>)> {
+    let score = SCORES.load(storage, address)?;
+    let seq = ENTRY_SEQ.load(storage, address)?;
+    let diff = update_member(storage, address, None, height)?;
+    LEADERBOARD.remove(storage, &rank_key(score, seq));
+    update_rank_counts(storage, &rank_key(score, seq), false)?;
+    ENTRY_SEQ.remove(storage, address);
+    KEY_KINDS.remove(storage, address);
+    PROFILES.remove(storage, address);
+    SCORES.remove(storage, address, height)?;
+    update_digest(storage, address, score, false)?;
+    update_total(storage, -i64::from(score), height)?;
+    Ok((score, diff))
+}
+
+/// Saves the member weight of the given address as of the given block
+/// height, or removes it if score is None, and keeps the total weight in
+/// sync. Only KeyKind::Address keys are members, and a negative score is
+/// a weight of 0. Returns the change to the member weight, if any.
+fn update_member(storage: &mut dyn Storage, address: &str, score: Option<i32>, height: u64) -> StdResult<Option<MemberDiff>> {
+    // Skip keys that are not addresses.
+    if KEY_KINDS.load(storage, address)? != KeyKind::Address {
+        return Ok(None);
+    }
+
+    // Skip updates that leave the weight as it is.
+    let old = MEMBERS.may_load(storage, address)?;
+    let new = score.map(|score| score.max(0) as u64);
+    if old == new {
+        return Ok(None);
+    }
+
+    // Save the new weight and total weight.
+    match new {
+        Some(weight) => MEMBERS.save(storage, address, &weight, height)?,
+        None => MEMBERS.remove(storage, address, height)?,
+    }
+    let total = TOTAL_WEIGHT.may_load(storage)?.unwrap_or_default();
+    TOTAL_WEIGHT.save(storage, &(total - old.unwrap_or_default() + new.unwrap_or_default()))?;
+    Ok(Some(MemberDiff::new(address, old, new)))
+}
+
+/// Returns the profile of the given key if include_profile is set and it has one.
+fn load_profile(storage: &dyn Storage, address: &str, include_profile: bool) -> StdResult<Option<Profile>> {
+    if !include_profile {
+        return Ok(None);
+    }
+    PROFILES.may_load(storage, address)
+}
+
+/// Returns the hooks of the given kind, ordered by address.
+fn load_hooks(storage: &dyn Storage, kind: HookKind) -> StdResult<Vec<Addr>> {
+    HOOKS
+        .prefix(kind.as_str())
+        .keys(storage, None, None, Order::Ascending)
+        .map(|addr| Ok(Addr::unchecked(String::from_utf8(addr)?)))
+        .collect()
+}
+
+/// Returns the messages that notify every hook of a write to the score
+/// at the given address. Score hooks get a ScoreChangedHookMsg with the
+/// old and new score, and Member hooks get a cw4 MemberChangedHookMsg
+/// iff the member weight changed.
+fn hook_msgs(
+    storage: &dyn Storage,
+    address: &str,
+    old_score: Option<i32>,
+    new_score: Option<i32>,
+    diff: Option<MemberDiff>,
+) -> StdResult<Vec<SubMsg>> {
+    let mut msgs = vec![];
+    for hook in load_hooks(storage, HookKind::Score)? {
+        let msg = ScoreChangedHookMsg { address: address.to_string(), old_score, new_score };
+        msgs.push(SubMsg::new(msg.into_cosmos_msg(hook)?));
+    }
+    if let Some(diff) = diff {
+        for hook in load_hooks(storage, HookKind::Member)? {
+            msgs.push(SubMsg::new(MemberChangedHookMsg::one(diff.clone()).into_cosmos_msg(hook)?));
+        }
+    }
+    Ok(msgs)
+}
+
+/// Adds or replaces (added) or removes (!added) the leaf of the
+/// (address, score) pair in the digest trie, and rehashes its branch.
+fn update_digest(storage: &mut dyn Storage, address: &str, score: i32, added: bool) -> StdResult<()> {
+    let path = Sha256::digest(address.as_bytes()).to_vec();
+    let root = DIGEST_ROOT.may_load(storage)?;
+    let root = match (added, root) {
+        (true, root) => {
+            let hash = Sha256::new().chain([0u8]).chain(score.to_be_bytes()).chain(address.as_bytes()).finalize();
+            Some(digest_insert(storage, root, &path, Binary::from(hash.to_vec()))?)
+        }
+        (false, Some(root)) => digest_remove(storage, root, &path)?,
+        (false, None) => return Err(StdError::not_found("digest leaf")),
+    };
+    match root {
+        Some(root) => DIGEST_ROOT.save(storage, &root),
+        None => {
+            DIGEST_ROOT.remove(storage);
+            Ok(())
+        }
+    }
+}
+
+/// Returns child with the leaf at path added, or its hash replaced.
+fn digest_insert(storage: &mut dyn Storage, child: Option<DigestChild>, path: &[u8], hash: Binary) -> StdResult<DigestChild> {
+    let leaf = DigestChild::Leaf { path: Binary::from(path), hash: hash.clone() };
+    let child = match child {
+        Some(child) => child,
+        None => return Ok(leaf),
+    };
+
+    // Find the first bit where the path leaves the child, if it does.
+    let split = match &child {
+        DigestChild::Leaf { path: other, .. } => first_different_bit(path, other),
+        DigestChild::Node { bit, prefix, .. } => first_different_bit(&path_prefix(path, *bit), prefix),
+    };
+    match (split, child) {
+        // The same path, so the same address.
+        (None, DigestChild::Leaf { .. }) => Ok(leaf),
+
+        // A path below the branch, so insert it on its side.
+        (None, DigestChild::Node { bit, prefix, .. }) => {
+            let DigestNode { left, right } = DIGEST_NODES.load(storage, &digest_node_key(bit, &prefix))?;
+            let (left, right) = match bit_at(path, bit) {
+                false => (digest_insert(storage, Some(left), path, hash)?, right),
+                true => (left, digest_insert(storage, Some(right), path, hash)?),
+            };
+            save_digest_node(storage, bit, prefix.to_vec(), left, right)
+        }
+
+        // A path beside the child, so branch off where they differ.
+        (Some(split), child) => match bit_at(path, split) {
+            false => save_digest_node(storage, split, path_prefix(path, split), leaf, child),
+            true => save_digest_node(storage, split, path_prefix(path, split), child, leaf),
+        },
+    }
+}
+
+/// Returns child with the leaf at path removed, or None if nothing is left.
+/// A branch left with one child is replaced by that child.
+fn digest_remove(storage: &mut dyn Storage, child: DigestChild, path: &[u8]) -> StdResult<Option<DigestChild>> {
+    match child {
+        DigestChild::Leaf { path: other, .. } if other.as_slice() == path => Ok(None),
+        DigestChild::Leaf { .. } => Err(StdError::not_found("digest leaf")),
+        DigestChild::Node { bit, prefix, .. } => {
+            let key = digest_node_key(bit, &prefix);
+            let DigestNode { left, right } = DIGEST_NODES.load(storage, &key)?;
+            let goes_right = bit_at(path, bit);
+            let (side, other) = if goes_right { (right, left) } else { (left, right) };
+            match digest_remove(storage, side, path)? {
+                None => {
+                    DIGEST_NODES.remove(storage, &key);
+                    Ok(Some(other))
+                }
+                Some(side) if goes_right => save_digest_node(storage, bit, prefix.to_vec(), other, side).map(Some),
+                Some(side) => save_digest_node(storage, bit, prefix.to_vec(), side, other).map(Some),
+            }
+        }
+    }
+}
+
+/// Saves the branch that splits on bit under prefix and returns it as a child.
+fn save_digest_node(storage: &mut dyn Storage, bit: u16, prefix: Vec<u8>, left: DigestChild, right: DigestChild) -> StdResult<DigestChild> {
+    let hash = Sha256::new()
+        .chain([1u8])
+        .chain(bit.to_be_bytes())
+        .chain(child_hash(&left).as_slice())
+        .chain(child_hash(&right).as_slice())
+        .finalize();
+    DIGEST_NODES.save(storage, &digest_node_key(bit, &prefix), &DigestNode { left, right })?;
+    Ok(DigestChild::Node { bit, prefix: Binary::from(prefix), hash: Binary::from(hash.to_vec()) })
+}
+
+/// Returns the hash of a child of the digest trie.
+fn child_hash(child: &DigestChild) -> &Binary {
+    match child {
+        DigestChild::Leaf { hash, .. } | DigestChild::Node { hash, .. } => hash,
+    }
+}
+
+/// Returns whether the given bit of path is set, counting from the most
+/// significant bit of the first byte.
+fn bit_at(path: &[u8], bit: u16) -> bool {
+    path[usize::from(bit / 8)] & (0x80 >> (bit % 8)) != 0
+}
+
+/// Returns path with every bit from the given bit on set to 0.
+fn path_prefix(path: &[u8], bit: u16) -> Vec<u8> {
+    path.iter()
+        .enumerate()
+        .map(|(index, byte)| match (index as u16 * 8, bit) {
+            (start, bit) if start + 8 <= bit => *byte,
+            (start, bit) if start >= bit => 0,
+            (start, bit) => byte & !(0xffu8 >> (bit - start)),
+        })
+        .collect()
+}
+
+/// Returns the first bit where two paths differ, if they do.
+fn first_different_bit(a: &[u8], b: &[u8]) -> Option<u16> {
+    a.iter()
+        .zip(b.iter())
+        .enumerate()
+        .find(|(_, (a, b))| a != b)
+        .map(|(index, (a, b))| index as u16 * 8 + (a ^ b).leading_zeros() as u16)
+}
+
+/// Returns the root hash of the digest trie, which is all zeros for an empty table.
+fn load_digest(storage: &dyn Storage) -> StdResult<[u8; 32]> {
+    let mut digest = [0u8; 32];
+    if let Some(root) = DIGEST_ROOT.may_load(storage)? {
+        let hash = child_hash(&root);
+        if hash.len() != digest.len() {
+            return Err(StdError::parse_err("[u8; 32]", "state digest must be 32 bytes"));
+        }
+        digest.copy_from_slice(hash.as_slice());
+    }
+    Ok(digest)
+}
+
+/// Adds change to the sum of every score as of the given block height.
+fn update_total(storage: &mut dyn Storage, change: i64, height: u64) -> StdResult<()> {
+    let total = TOTAL.may_load(storage, TOTAL_KEY)?.unwrap_or_default();
+    TOTAL.save(storage, TOTAL_KEY, &(total + change), height)
+}
+
+/// Appends a record of a change to the history of the given address, then
+/// drops its oldest records until at most config.max_history are kept.
+/// Every handler that writes a score must call this before writing it.
+fn append_history(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    address: &str,
+    old_score: Option<i32>,
+    new_score: Option<i32>,
+    memo: Option<String>,
+) -> Result<(), ContractError> {
+    // Error if the memo is too long.
+    if let Some(memo) = &memo {
+        if memo.len() > MAX_MEMO_LENGTH as usize {
+            return Err(ContractError::MemoTooLong { length: memo.len() as u64, max: MAX_MEMO_LENGTH });
+        }
+    }
+
+    // Save the record under the next id.
+    let mut range = HISTORY_RANGE.may_load(storage, address)?.unwrap_or_default();
+    let record = HistoryRecord {
+        id: range.next,
+        old_score,
+        new_score,
+        sender: sender.clone(),
+        height: env.block.height,
+        time: env.block.time,
+        memo,
+    };
+    HISTORY.save(storage, (address, U64Key::new(range.next)), &record)?;
+    range.next += 1;
+
+    // Drop the oldest records beyond the retention cap.
+    let max_history = CONFIG.load(storage)?.max_history;
+    while range.next - range.first > u64::from(max_history) {
+        HISTORY.remove(storage, (address, U64Key::new(range.first)));
+        range.first += 1;
+    }
+    HISTORY_RANGE.save(storage, address, &range)?;
+    Ok(())
+}
+
+/// Returns the leaderboard key of a stored address.
+fn load_rank_key(storage: &dyn Storage, address: &str) -> StdResult<Vec<u8>> {
+    let score = SCORES.load(storage, address)?;
+    let seq = ENTRY_SEQ.load(storage, address)?;
+    Ok(rank_key(score, seq))
+}
+
+// ======================================================================
+// Testing Block
+// ======================================================================
+
+#[cfg(test)]
+mod tests {
+    // Imports for testing purposes.
+    use super::*;
+    use cosmwasm_std::testing::{MockApi, mock_dependencies, mock_env, mock_info, MockQuerier, MockStorage};
+    use crate::state::LegacyState;
+    use cw_storage_plus::Map;
+    use cosmwasm_std::{attr, coins, from_binary, Addr, OwnedDeps};
+
+    /// A "DO BEFORE EACH" testing utility function.
+    /// Returns the parameters necessary to instantiate a smart contract.
+    pub fn setup() -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, MessageInfo, InstantiateMsg) {
+        // Mock out dependencies.
+        let deps = mock_dependencies(&[]);
+
+        // Mock out account info.
+        let info = mock_info("owner", &coins(1000, "earth"));
+
+        // Create a message that could be used to instantiate a smart contract.
+        let msg = InstantiateMsg {
+            first_address: Some("1".to_string()),
+            first_address_score: 10,
+            initial_scores: vec![],
+            owner: None,
+            allow_external_keys: true,
+            open_registration: false,
+            default_score: 0,
+            max_batch_size: None,
+            min_score: None,
+            max_score: None,
+            max_delta: None,
+            max_history: None,
+        };
+
+        // Return all three to be used in test cases.
+        (deps, info, msg)
+    }
+
+    // ===========================
+    // VERBOSE REQUIREMENT TESTS
+    // ===========================
+
+    /// Test instantiating the contract and setting the owner.
+    #[test]
+    fn instantiate_contract_and_set_owner() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Ensure instantiation was successful.
+        assert_eq!(0, res.messages.len());
+    }
+
+    /// Test making a read query to get the owner of the smart contract.
+    #[test]
+    fn support_a_read_query_to_get_the_owner_of_the_start_contract() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract. 
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Ensure the read query returns the owner of the smart contract. 
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwner { board: None }).unwrap();
+        let value: OwnerResponse = from_binary(&res).unwrap();
+        assert_eq!(Some(Addr::unchecked("owner")), value.owner);
+    }
+
+    /// Testing storing scores for different addresses in the smart contract state.
+    /// (ex. {address_1: 10, address_2: 20})
+    #[test]
+    fn store_the_score_for_different_addresses_in_the_smart_contract_state() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Call AddAddress.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let new_address = "2".to_string();
+        let new_score = 20;
+        let msg = ExecuteMsg::AddAddress { new_address, new_score, memo: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Ensure Address1's score is 10.
+        let address = "1".to_string();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some(address), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, 10);
+
+        // Ensure Address2's score is 20.
+        let address = "2".to_string();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some(address), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, 20);
+    }
+
+    /// Testing support for an execute message where only the owner
+    /// of the smart contract can set the score of an address.
+    /// Case: Owner.
+    #[test]
+    fn set_by_owner() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Execute Set as owner.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21, memo: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Make sure Address1's score is 21.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("1".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, 21);
+    }
+
+    /// Testing support for an execute message where only the owner
+    /// of the smart contract can set the score of an address.
+    /// Case: Non-owner.
+    #[test]
+    fn set_by_anyone() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Execute Set as a non-owner.
+        let info = mock_info("anyone", &coins(2, "token"));
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21, memo: None, board: None };
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        
+        // Check if the program errors.
+        match res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Ensure Address1's score is still 10.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("1".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, 10);
+    }
+
+    /// Test support for a read query to get the score for a particular address.
+    #[test]
+    fn read_query_to_get_the_score_of_particular_address() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // EnsEnsure Address1's score is still 10.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("1".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, 10);
+    }
+
+    // ===========================
+    // UNIT TESTS
+    // ===========================
+
+    /// Ensure one cannot set at an invalid address.
+    #[test]
+    fn set_by_owner_at_invalid_address() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Execute Set at an address that is not in our state HashMap.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::Set { address: "2".to_string(), new_score: 21, memo: None, board: None };
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        
+        // Check if the program errors.
+        match res {
+            Err(ContractError::AddressNotFound { address }) => assert_eq!(address, "2"),
+            _ => panic!("Must provide a valid address to set."),
+        }
+    }
+
+    /// Ensure one cannot add an address if it already exists.
+    #[test]
+    fn error_if_adding_to_existing_address() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Call AddAddress with an invalid address.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let new_address = "1".to_string();
+        let new_score = 20;
+        let msg = ExecuteMsg::AddAddress { new_address, new_score, memo: None, board: None };
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+
+        // Check if the program errors.
+        match res {
+            Err(ContractError::AddressAlreadyExists { address }) => assert_eq!(address, "1"),
+            _ => panic!("Must return address already exists error"),
+        }
+    }
+
+    /// Testing storing scores for lots of addresses in the smart contract state.
+    #[test]
+    fn lots_of_addresses() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        for n in 2..47 {
+            // Call AddAddress.
+            let info = mock_info("owner", &coins(1000, "earth"));
+            let new_address = n.to_string();
+            let new_score = n * 10;
+            let msg = ExecuteMsg::AddAddress { new_address, new_score, memo: None, board: None };
+            let _res = execute(deps.as_mut(), mock_env(), info, msg);
+        }
+
+        for n in 1..47 {
+            // Ensure Address{n}'s score is n * 10.
+            let address = n.to_string();
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some(address), include_profile: false, board: None, name: None }).unwrap();
+            let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+            assert_eq!(value.score, n * 10);
+        }
+    }
+
+    /// Testing storing scores for lots of addresses in the smart contract state.
+    #[test]
+    fn store_lots_of_addresses_then_set_them() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        for n in 2..20 {
+            // Call AddAddress.
+            let info = mock_info("owner", &coins(1000, "earth"));
+            let new_address = n.to_string();
+            let new_score = n * 10;
+            let msg = ExecuteMsg::AddAddress { new_address, new_score, memo: None, board: None };
+            let _res = execute(deps.as_mut(), mock_env(), info, msg);
+        }
+
+        for n in 1..20 {
+            // Ensure Address{n}'s score is n * 10.
+            let address = n.to_string();
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some(address), include_profile: false, board: None, name: None }).unwrap();
+            let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+            assert_eq!(value.score, n * 10);
+        }
+
+        for n in 1..20 {
+            // Execute Set as owner.
+            let info = mock_info("owner", &coins(1000, "earth"));
+            let msg = ExecuteMsg::Set { address: n.to_string(), new_score: 100, memo: None, board: None };
+            let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        }
+        
+        for n in 1..20 {
+            // Make sure Address1's score is 100.
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some(n.to_string()), include_profile: false, board: None, name: None }).unwrap();
+            let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+            assert_eq!(value.score, 100);
+        }
+    }
+
+    /// Testing owners of different smart contracts can't Set each other's scores.
+    #[test]
+    fn cross_set() {
+        // Mock out dependencies.
+        let mut deps1 = mock_dependencies(&[]);
+        let mut deps2 = mock_dependencies(&[]);
+
+        // Mock out account info.
+        let info1 = mock_info("Alice", &coins(1000, "earth"));
+        let info2 = mock_info("Bob", &coins(2, "token"));
+
+        // Create a message that could be used to instantiate a smart contract.
+        let msg1 = InstantiateMsg {
+            first_address: Some("1".to_string()),
+            first_address_score: 5,
+            initial_scores: vec![],
+            owner: None,
+            allow_external_keys: true,
+            open_registration: false,
+            default_score: 0,
+            max_batch_size: None,
+            min_score: None,
+            max_score: None,
+            max_delta: None,
+            max_history: None,
+        };
+        let msg2 = InstantiateMsg {
+            first_address: Some("1".to_string()),
+            first_address_score: 17,
+            initial_scores: vec![],
+            owner: None,
+            allow_external_keys: true,
+            open_registration: false,
+            default_score: 0,
+            max_batch_size: None,
+            min_score: None,
+            max_score: None,
+            max_delta: None,
+            max_history: None,
+        };
+    
+        // Instantiate both smart contracts.
+        let res1 = instantiate(deps1.as_mut(), mock_env(), info1, msg1).unwrap();
+        assert_eq!(0, res1.messages.len());
+        let res2 = instantiate(deps2.as_mut(), mock_env(), info2, msg2).unwrap();
+        assert_eq!(0, res2.messages.len());
+
+        // Reset the infos because they were moved.
+        let info1 = mock_info("Alice", &coins(1000, "earth"));
+        let info2 = mock_info("Bob", &coins(2, "token"));
+
+        // Try to execute Set on Alice's contract as Bob.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 0, memo: None, board: None };
+        let res = execute(deps1.as_mut(), mock_env(), info2, msg);
+        match res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Ensure Alice's Address1 score is still 5.
+        let res = query(deps1.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("1".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, 5);
+
+        // Try to execute Set on Bob's contract as Alice.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 0, memo: None, board: None };
+        let res = execute(deps2.as_mut(), mock_env(), info1, msg);
+        match res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Ensure Bob's Address1 score is still 17.
+        let res = query(deps2.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("1".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, 17);
+    }
+
+    /// Ensure each score is stored under its own key instead of in the state.
+    #[test]
+    fn scores_are_stored_per_address() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Call AddAddress.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::AddAddress { new_address: "2".to_string(), new_score: 20, memo: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Ensure both scores can be loaded directly from the scores Map.
+        assert_eq!(SCORES.load(&deps.storage, "1").unwrap(), 10);
+        assert_eq!(SCORES.load(&deps.storage, "2").unwrap(), 20);
+
+        // Ensure GetHash still returns the whole table as a JSON String.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetHash { board: None }).unwrap();
+        let value: HashResponse = from_binary(&res).unwrap();
+        let hash: BTreeMap<String, i32> = serde_json::from_str(&value.hash).unwrap();
+        assert_eq!(hash.len(), 2);
+        assert_eq!(hash["1"], 10);
+        assert_eq!(hash["2"], 20);
+    }
+
+    /// A testing utility function.
+    /// Returns dependencies holding a legacy JSON-blob state saved by version 0.1.0.
+    fn setup_legacy(hash: &str) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        // Mock out dependencies.
+        let mut deps = mock_dependencies(&[]);
+
+        // Save the state the way version 0.1.0 did.
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+        let legacy = LegacyState {
+            owner: Addr::unchecked("owner"),
+            hash: hash.to_string(),
+        };
+        LEGACY_STATE.save(&mut deps.storage, &legacy).unwrap();
+
+        deps
+    }
+
+    /// Ensure migrating a legacy contract keeps the owner and every score.
+    #[test]
+    fn migrate_legacy_state_keeps_every_score() {
+        // Create a legacy contract with three scores.
+        let mut deps = setup_legacy(r#"{"1":10,"2":20,"3":-30}"#);
+
+        // Migrate the contract.
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Ensure every score made it into the scores Map.
+        for (address, score) in &[("1", 10), ("2", 20), ("3", -30)] {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some(address.to_string()), include_profile: false, board: None, name: None }).unwrap();
+            let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+            assert_eq!(value.score, *score);
+        }
+
+        // Ensure unknown addresses still report a score of 1.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("4".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value, ScoreFromAddressResponse { address: "4".to_string(), score: 1, exists: false, kind: None, profile: None });
+
+        // Ensure the leaderboard index was built.
+        let top = leaderboard_addresses(deps.as_ref(), QueryMsg::TopScores { start_after: None, limit: None, include_profile: false, board: None });
+        assert_eq!(top, vec!["2", "1", "3"]);
+
+        // Ensure the owner is unchanged.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwner { board: None }).unwrap();
+        let value: OwnerResponse = from_binary(&res).unwrap();
+        assert_eq!(Some(Addr::unchecked("owner")), value.owner);
+
+        // Ensure the owner can still set scores after migrating.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::Set { address: "2".to_string(), new_score: 21, memo: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Ensure the stored version was bumped.
+        let version = get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.version, CONTRACT_VERSION);
+    }
+
+    /// Ensure migrating refuses foreign contracts and downgrades.
+    #[test]
+    fn migrate_rejects_foreign_contracts_and_downgrades() {
+        // Try to migrate a different contract.
+        let mut deps = setup_legacy("{}");
+        set_contract_version(&mut deps.storage, "crates.io:cw20-base", "0.1.0").unwrap();
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {});
+        match res {
+            Err(ContractError::CannotMigrate { previous_contract }) => assert_eq!(previous_contract, "crates.io:cw20-base"),
+            _ => panic!("Must return cannot migrate error"),
+        }
+
+        // Try to migrate from a newer version.
+        let mut deps = setup_legacy("{}");
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "99.0.0").unwrap();
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {});
+        match res {
+            Err(ContractError::CannotMigrateVersion { previous_version, .. }) => assert_eq!(previous_version, "99.0.0"),
+            _ => panic!("Must return cannot migrate version error"),
+        }
+    }
+
+    /// Ensure ListScores walks the whole table in pages ordered by address.
+    #[test]
+    fn list_scores_in_pages() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        for n in 2..47 {
+            // Call AddAddress.
+            let info = mock_info("owner", &coins(1000, "earth"));
+            let msg = ExecuteMsg::AddAddress { new_address: n.to_string(), new_score: n * 10, memo: None, board: None };
+            let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        }
+
+        // Ensure the page size is clamped to the maximum.
+        let msg = QueryMsg::ListScores { start_after: None, limit: Some(1000), include_profile: false, board: None };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: ListScoresResponse = from_binary(&res).unwrap();
+        assert_eq!(value.scores.len(), MAX_LIMIT as usize);
+
+        // Walk every page using the default page size.
+        let mut entries: Vec<ScoreEntry> = vec![];
+        let mut start_after = None;
+        loop {
+            let msg = QueryMsg::ListScores { start_after, limit: None, include_profile: false, board: None };
+            let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+            let value: ListScoresResponse = from_binary(&res).unwrap();
+            assert!(value.scores.len() <= DEFAULT_LIMIT as usize);
+            match value.scores.last() {
+                Some(last) => start_after = Some(last.address.clone()),
+                None => break,
+            }
+            entries.extend(value.scores);
+        }
+
+        // Ensure every entry was returned once, ordered by address.
+        let mut expected: Vec<String> = (1..47).map(|n| n.to_string()).collect();
+        expected.sort();
+        let addresses: Vec<String> = entries.iter().map(|entry| entry.address.clone()).collect();
+        assert_eq!(addresses, expected);
+        for entry in entries {
+            assert_eq!(entry.score, entry.address.parse::<i32>().unwrap() * 10);
+        }
+    }
+
+    /// A testing utility function.
+    /// Returns the addresses of a leaderboard query in order.
+    fn leaderboard_addresses(deps: Deps, msg: QueryMsg) -> Vec<String> {
+        let res = query(deps, mock_env(), msg).unwrap();
+        let value: ListScoresResponse = from_binary(&res).unwrap();
+        value.scores.into_iter().map(|entry| entry.address).collect()
+    }
+
+    /// Ensure Rank matches the TopScores position of every address, across
+    /// ties and scores that differ in every byte of the leaderboard key.
+    #[test]
+    fn rank_matches_top_scores() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract. Address1's score is 10.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Add addresses with extreme, tied and nearby scores.
+        let scores = [i32::MAX, i32::MIN, 0, -1, 255, 256, 65_536, 10, -65_536, 0];
+        for index in 2..28 {
+            let info = mock_info("owner", &coins(1000, "earth"));
+            let msg = ExecuteMsg::AddAddress { new_address: index.to_string(), new_score: scores[index % scores.len()], memo: None, board: None };
+            let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        }
+
+        // Move a few addresses, including onto an existing tie.
+        for (address, score) in &[("3", 0), ("12", i32::MIN), ("20", 256)] {
+            let info = mock_info("owner", &coins(1000, "earth"));
+            let msg = ExecuteMsg::Set { address: address.to_string(), new_score: *score, memo: None, board: None };
+            let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        }
+
+        // Ensure every rank is the position in TopScores.
+        let top = leaderboard_addresses(deps.as_ref(), QueryMsg::TopScores { start_after: None, limit: Some(MAX_LIMIT), include_profile: false, board: None });
+        assert_eq!(top.len(), 27);
+        for (index, address) in top.iter().enumerate() {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::Rank { address: Some(address.clone()), board: None, name: None }).unwrap();
+            let value: RankResponse = from_binary(&res).unwrap();
+            assert_eq!(value.rank, index as u64 + 1);
+        }
+    }
+
+    /// Ensure TopScores, BottomScores and Rank follow scores and break ties by insertion.
+    #[test]
+    fn leaderboard_orders_by_score_then_insertion() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract. Address1's score is 10.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Add addresses with a tie and a negative score.
+        for (address, score) in &[("2", 30), ("3", 10), ("4", -5), ("5", 20)] {
+            let info = mock_info("owner", &coins(1000, "earth"));
+            let msg = ExecuteMsg::AddAddress { new_address: address.to_string(), new_score: *score, memo: None, board: None };
+            let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        }
+
+        // Ensure the highest score comes first and Address1 beats Address3 on the tie.
+        let top = leaderboard_addresses(deps.as_ref(), QueryMsg::TopScores { start_after: None, limit: None, include_profile: false, board: None });
+        assert_eq!(top, vec!["2", "5", "1", "3", "4"]);
+
+        // Ensure BottomScores is the exact reverse.
+        let bottom = leaderboard_addresses(deps.as_ref(), QueryMsg::BottomScores { start_after: None, limit: None, include_profile: false, board: None });
+        assert_eq!(bottom, vec!["4", "3", "1", "5", "2"]);
+
+        // Ensure pages continue after the given address.
+        let msg = QueryMsg::TopScores { start_after: Some("5".to_string()), limit: Some(2), include_profile: false, board: None };
+        assert_eq!(leaderboard_addresses(deps.as_ref(), msg), vec!["1", "3"]);
+
+        // Ensure Rank agrees with TopScores.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Rank { address: Some("3".to_string()), board: None, name: None }).unwrap();
+        let value: RankResponse = from_binary(&res).unwrap();
+        assert_eq!(value, RankResponse { address: "3".to_string(), score: 10, rank: 4 });
+
+        // Execute Set as owner to move Address4 to the top.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::Set { address: "4".to_string(), new_score: 99, memo: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Ensure the index followed the update without leaving the old entry behind.
+        let top = leaderboard_addresses(deps.as_ref(), QueryMsg::TopScores { start_after: None, limit: None, include_profile: false, board: None });
+        assert_eq!(top, vec!["4", "2", "5", "1", "3"]);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Rank { address: Some("4".to_string()), board: None, name: None }).unwrap();
+        let value: RankResponse = from_binary(&res).unwrap();
+        assert_eq!(value.rank, 1);
+
+        // Ensure ranking an unknown address errors.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Rank { address: Some("6".to_string()), board: None, name: None });
+        assert!(res.is_err());
+    }
+
+    /// Ensure empty addresses are rejected with InvalidAddress.
+    #[test]
+    fn error_if_address_is_empty() {
+        // Try to instantiate the contract with an empty first address.
+        let (mut deps, info, mut msg) = setup();
+        msg.first_address = Some("".to_string());
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg);
+        match res {
+            Err(ContractError::InvalidAddress { address }) => assert_eq!(address, ""),
+            _ => panic!("Must return invalid address error"),
+        }
+
+        // Instantiate the contract.
+        let (mut deps, info, msg) = setup();
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Call AddAddress with a blank address.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::AddAddress { new_address: "  ".to_string(), new_score: 20, memo: None, board: None };
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        match res {
+            Err(ContractError::InvalidAddress { address }) => assert_eq!(address, "  "),
+            _ => panic!("Must return invalid address error"),
+        }
+    }
+
+    /// Ensure permission failures win over data errors.
+    #[test]
+    fn set_by_anyone_at_invalid_address() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Execute Set as a non-owner at an address that is not stored.
+        let info = mock_info("anyone", &coins(2, "token"));
+        let msg = ExecuteMsg::Set { address: "2".to_string(), new_score: 21, memo: None, board: None };
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        match res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+    }
+
+    /// Ensure migrating malformed legacy state errors instead of panicking.
+    #[test]
+    fn migrate_rejects_malformed_legacy_state() {
+        // Try to migrate a legacy hash that is not JSON.
+        let mut deps = setup_legacy("not json");
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {});
+        match res {
+            Err(ContractError::InvalidLegacyState { reason }) => assert!(reason.starts_with("cannot parse hash")),
+            _ => panic!("Must return invalid legacy state error"),
+        }
+
+        // Try to migrate a legacy hash with a score that is not an i32.
+        let mut deps = setup_legacy(r#"{"1":"ten"}"#);
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {});
+        match res {
+            Err(ContractError::InvalidLegacyState { .. }) => {}
+            _ => panic!("Must return invalid legacy state error"),
+        }
+
+        // Try to migrate a legacy state that cannot be loaded at all.
+        let mut deps = setup_legacy("{}");
+        deps.storage.set(b"state", b"{\"owner\":7}");
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {});
+        match res {
+            Err(ContractError::Std(StdError::ParseErr { .. })) => {}
+            _ => panic!("Must return parse error"),
+        }
+    }
+
+    /// Ensure corrupted scores and state surface as errors instead of panics.
+    #[test]
+    fn corrupted_state_fails_cleanly() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Overwrite Address1's score with something that is not an i32.
+        deps.storage.set(&SCORES.key("1"), b"\"ten\"");
+
+        // Ensure every query that reads the score errors.
+        let msgs = vec![
+            QueryMsg::GetScoreFromAddress { address: Some("1".to_string()), include_profile: false, board: None, name: None },
+            QueryMsg::GetHash { board: None },
+            QueryMsg::ListScores { start_after: None, limit: None, include_profile: false, board: None },
+            QueryMsg::TopScores { start_after: None, limit: None, include_profile: false, board: None },
+            QueryMsg::Rank { address: Some("1".to_string()), board: None, name: None },
+        ];
+        for msg in msgs {
+            match query(deps.as_ref(), mock_env(), msg) {
+                Err(StdError::ParseErr { target_type, .. }) => assert_eq!(target_type, "i32"),
+                res => panic!("Must return parse error, got {:?}", res),
+            }
+        }
+
+        // Ensure setting the corrupted score errors.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21, memo: None, board: None };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::Std(StdError::ParseErr { .. })) => {}
+            _ => panic!("Must return parse error"),
+        }
+
+        // Store a score under a key that is not UTF-8 and ensure listing errors.
+        deps.storage.set(&SCORES.key("1"), b"10");
+        let raw_scores: Map<&[u8], i32> = Map::new("scores");
+        raw_scores.save(&mut deps.storage, &[0xff, 0xfe], &10).unwrap();
+        match query(deps.as_ref(), mock_env(), QueryMsg::ListScores { start_after: None, limit: None, include_profile: false, board: None }) {
+            Err(StdError::InvalidUtf8 { .. }) => {}
+            res => panic!("Must return invalid utf8 error, got {:?}", res),
+        }
+
+        // Overwrite the state and ensure reading the owner errors.
+        deps.storage.set(b"state", b"[]");
+        match query(deps.as_ref(), mock_env(), QueryMsg::GetOwner { board: None }) {
+            Err(StdError::ParseErr { .. }) => {}
+            res => panic!("Must return parse error, got {:?}", res),
+        }
+    }
+
+    /// Ensure addresses are validated and normalized before they are stored.
+    #[test]
+    fn addresses_are_validated_and_normalized() {
+        // Instantiate the contract without external keys.
+        let (mut deps, info, mut msg) = setup();
+        msg.first_address = Some("Terra1Alice".to_string());
+        msg.allow_external_keys = false;
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Ensure the first address was stored lowercased as an address.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("terra1alice".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value, ScoreFromAddressResponse { address: "terra1alice".to_string(), score: 10, exists: true, kind: Some(KeyKind::Address), profile: None });
+
+        // Ensure a differently cased copy of the address is a duplicate.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::AddAddress { new_address: "TERRA1ALICE".to_string(), new_score: 20, memo: None, board: None };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::AddressAlreadyExists { address }) => assert_eq!(address, "terra1alice"),
+            _ => panic!("Must return address already exists error"),
+        }
+
+        // Ensure Set finds the address regardless of case.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::Set { address: "terra1ALICE".to_string(), new_score: 21, memo: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("Terra1Alice".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, 21);
+
+        // Ensure keys that fail validation are rejected.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::AddAddress { new_address: "1".to_string(), new_score: 20, memo: None, board: None };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::InvalidAddress { address }) => assert_eq!(address, "1"),
+            _ => panic!("Must return invalid address error"),
+        }
+    }
+
+    /// Ensure external keys are stored verbatim and reported as such.
+    #[test]
+    fn external_keys_are_reported() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract with external keys allowed.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Call AddAddress with a valid address.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::AddAddress { new_address: "Terra1Bob".to_string(), new_score: 20, memo: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Ensure ListScores reports the kind of each key.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ListScores { start_after: None, limit: None, include_profile: false, board: None }).unwrap();
+        let value: ListScoresResponse = from_binary(&res).unwrap();
+        assert_eq!(value.scores, vec![
+            ScoreEntry { address: "1".to_string(), score: 10, kind: KeyKind::External, profile: None },
+            ScoreEntry { address: "terra1bob".to_string(), score: 20, kind: KeyKind::Address, profile: None },
+        ]);
+
+        // Ensure an unknown key has no kind.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("2".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.kind, None);
+    }
+
+    /// Ensure migrating refuses legacy addresses that collide once normalized.
+    #[test]
+    fn migrate_rejects_colliding_addresses() {
+        // Create a legacy contract with two spellings of one address.
+        let mut deps = setup_legacy(r#"{"Terra1Alice":1,"terra1alice":2}"#);
+
+        // Try to migrate the contract.
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {});
+        match res {
+            Err(ContractError::InvalidLegacyState { reason }) => assert!(reason.contains("terra1alice")),
+            _ => panic!("Must return invalid legacy state error"),
+        }
+    }
+
+    /// Ensure only the owner can add addresses while registration is closed.
+    #[test]
+    fn add_address_by_anyone() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Call AddAddress as a non-owner, even for their own address.
+        let info = mock_info("anyone", &coins(2, "token"));
+        let msg = ExecuteMsg::AddAddress { new_address: "anyone".to_string(), new_score: 0, memo: None, board: None };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+    }
+
+    /// Ensure open registration only lets senders add themselves with the default score.
+    #[test]
+    fn open_registration() {
+        // Instantiate the contract with open registration.
+        let (mut deps, info, mut msg) = setup();
+        msg.open_registration = true;
+        msg.default_score = 5;
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Try to register someone else's address.
+        let info = mock_info("anyone", &coins(2, "token"));
+        let msg = ExecuteMsg::AddAddress { new_address: "someone".to_string(), new_score: 5, memo: None, board: None };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Try to register with a score other than the default.
+        let info = mock_info("anyone", &coins(2, "token"));
+        let msg = ExecuteMsg::AddAddress { new_address: "anyone".to_string(), new_score: 100, memo: None, board: None };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::InvalidRegistrationScore { expected }) => assert_eq!(expected, 5),
+            _ => panic!("Must return invalid registration score error"),
+        }
+
+        // Register the sender's own address with the default score.
+        let info = mock_info("anyone", &coins(2, "token"));
+        let msg = ExecuteMsg::AddAddress { new_address: "Anyone".to_string(), new_score: 5, memo: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("anyone".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, 5);
+
+        // Ensure the owner can still add any address with any score.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::AddAddress { new_address: "someone".to_string(), new_score: 100, memo: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    /// Ensure unknown addresses are reported as missing with the default score.
+    #[test]
+    fn read_query_for_unknown_address() {
+        // Instantiate the contract with a default score of 1.
+        let (mut deps, info, mut msg) = setup();
+        msg.default_score = 1;
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Ensure an unknown address reports the default score and does not exist.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("2".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value, ScoreFromAddressResponse { address: "2".to_string(), score: 1, exists: false, kind: None, profile: None });
+
+        // Call AddAddress with a real score of 1.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::AddAddress { new_address: "2".to_string(), new_score: 1, memo: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Ensure the stored score of 1 can be told apart from the default.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("2".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value, ScoreFromAddressResponse { address: "2".to_string(), score: 1, exists: true, kind: Some(KeyKind::External), profile: None });
+    }
+
+    /// Ensure ownership only moves once the proposed owner accepts it.
+    #[test]
+    fn two_step_ownership_transfer() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Try to propose a new owner as a non-owner.
+        let info = mock_info("anyone", &coins(2, "token"));
+        let msg = ExecuteMsg::ProposeOwner { new_owner: "anyone".to_string(), expiry: None, board: None };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Propose a new owner as the owner.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::ProposeOwner { new_owner: "new_owner".to_string(), expiry: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Ensure the proposal is pending and the owner has not changed.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner { board: None }).unwrap();
+        let value: PendingOwnerResponse = from_binary(&res).unwrap();
+        assert_eq!(value.new_owner, Some(Addr::unchecked("new_owner")));
+        assert_eq!(value.expiry, Some(Expiration::Never {}));
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwner { board: None }).unwrap();
+        let value: OwnerResponse = from_binary(&res).unwrap();
+        assert_eq!(value.owner, Some(Addr::unchecked("owner")));
+
+        // Try to accept ownership as someone else.
+        let info = mock_info("anyone", &coins(2, "token"));
+        match execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptOwnership { board: None }) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Accept ownership as the proposed owner.
+        let info = mock_info("new_owner", &coins(2, "token"));
+        let _res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptOwnership { board: None }).unwrap();
+
+        // Ensure the owner changed and the proposal is gone.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwner { board: None }).unwrap();
+        let value: OwnerResponse = from_binary(&res).unwrap();
+        assert_eq!(value.owner, Some(Addr::unchecked("new_owner")));
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner { board: None }).unwrap();
+        let value: PendingOwnerResponse = from_binary(&res).unwrap();
+        assert_eq!(value, PendingOwnerResponse { new_owner: None, expiry: None });
+
+        // Ensure the old owner can no longer set and the new owner can.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21, memo: None, board: None };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+        let info = mock_info("new_owner", &coins(2, "token"));
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21, memo: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    /// Ensure ownership proposals can expire and be cancelled.
+    #[test]
+    fn ownership_proposal_expires_and_cancels() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Propose a new owner that must accept within 10 blocks.
+        let env = mock_env();
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let expiry = Expiration::AtHeight(env.block.height + 10);
+        let msg = ExecuteMsg::ProposeOwner { new_owner: "new_owner".to_string(), expiry: Some(expiry), board: None };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // Try to accept ownership once the proposal has expired.
+        let mut late = env.clone();
+        late.block.height += 10;
+        let info = mock_info("new_owner", &coins(2, "token"));
+        match execute(deps.as_mut(), late, info, ExecuteMsg::AcceptOwnership { board: None }) {
+            Err(ContractError::OwnershipProposalExpired {}) => {}
+            _ => panic!("Must return ownership proposal expired error"),
+        }
+
+        // Cancel the proposal as the owner.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let _res = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::CancelOwnershipProposal { board: None }).unwrap();
+
+        // Ensure the proposal can no longer be accepted or cancelled.
+        let info = mock_info("new_owner", &coins(2, "token"));
+        match execute(deps.as_mut(), env.clone(), info, ExecuteMsg::AcceptOwnership { board: None }) {
+            Err(ContractError::NoPendingOwner {}) => {}
+            _ => panic!("Must return no pending owner error"),
+        }
+        let info = mock_info("owner", &coins(1000, "earth"));
+        match execute(deps.as_mut(), env, info, ExecuteMsg::CancelOwnershipProposal { board: None }) {
+            Err(ContractError::NoPendingOwner {}) => {}
+            _ => panic!("Must return no pending owner error"),
+        }
+    }
+
+    /// Ensure renouncing ownership leaves nobody in control.
+    #[test]
+    fn renounce_ownership() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Propose a new owner, then renounce ownership as the owner.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::ProposeOwner { new_owner: "new_owner".to_string(), expiry: None, board: None };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let _res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::RenounceOwnership { board: None }).unwrap();
+
+        // Ensure there is no owner and the proposal was cleared.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwner { board: None }).unwrap();
+        let value: OwnerResponse = from_binary(&res).unwrap();
+        assert_eq!(value.owner, None);
+        let info = mock_info("new_owner", &coins(2, "token"));
+        match execute(deps.as_mut(), mock_env(), info, ExecuteMsg::AcceptOwnership { board: None }) {
+            Err(ContractError::NoPendingOwner {}) => {}
+            _ => panic!("Must return no pending owner error"),
+        }
+
+        // Ensure the former owner can no longer set.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21, memo: None, board: None };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+    }
+
+    /// A testing utility function.
+    /// Executes the message as the given sender.
+    fn execute_as(deps: DepsMut, sender: &str, msg: ExecuteMsg) -> Result<Response, ContractError> {
+        execute(deps, mock_env(), mock_info(sender, &coins(1000, "earth")), msg)
+    }
+
+    /// Ensure writers can set, registrars can add, and revoked roles stop working.
+    #[test]
+    fn roles_gate_writers_and_registrars() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Grant the writer and registrar roles as the owner.
+        let msg = ExecuteMsg::GrantRole { role: Role::Writer, address: "writer".to_string(), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let msg = ExecuteMsg::GrantRole { role: Role::Registrar, address: "registrar".to_string(), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+
+        // Ensure the writer can set but not add.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "writer", msg).unwrap();
+        let msg = ExecuteMsg::AddAddress { new_address: "2".to_string(), new_score: 20, memo: None, board: None };
+        match execute_as(deps.as_mut(), "writer", msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Ensure the registrar can add any address but not set.
+        let msg = ExecuteMsg::AddAddress { new_address: "2".to_string(), new_score: 20, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "registrar", msg).unwrap();
+        let msg = ExecuteMsg::Set { address: "2".to_string(), new_score: 21, memo: None, board: None };
+        match execute_as(deps.as_mut(), "registrar", msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Revoke the writer role and ensure the writer can no longer set.
+        let msg = ExecuteMsg::RevokeRole { role: Role::Writer, address: "writer".to_string(), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 22, memo: None, board: None };
+        match execute_as(deps.as_mut(), "writer", msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Ensure revoking twice names the role and address.
+        let msg = ExecuteMsg::RevokeRole { role: Role::Writer, address: "writer".to_string(), board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::RoleNotGranted { role, address }) => assert_eq!((role.as_str(), address.as_str()), ("writer", "writer")),
+            _ => panic!("Must return role not granted error"),
+        }
+    }
+
+    /// Ensure admins manage writers and registrars but only the owner manages admins.
+    #[test]
+    fn admins_manage_roles() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Try to grant a role as a non-admin.
+        let msg = ExecuteMsg::GrantRole { role: Role::Writer, address: "anyone".to_string(), board: None };
+        match execute_as(deps.as_mut(), "anyone", msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Grant the admin role as the owner, then grant writers as the admin.
+        let msg = ExecuteMsg::GrantRole { role: Role::Admin, address: "admin".to_string(), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        for writer in &["writer_b", "writer_a"] {
+            let msg = ExecuteMsg::GrantRole { role: Role::Writer, address: writer.to_string(), board: None };
+            let _res = execute_as(deps.as_mut(), "admin", msg).unwrap();
+        }
+
+        // Ensure granting twice errors.
+        let msg = ExecuteMsg::GrantRole { role: Role::Writer, address: "writer_a".to_string(), board: None };
+        match execute_as(deps.as_mut(), "admin", msg) {
+            Err(ContractError::RoleAlreadyGranted { .. }) => {}
+            _ => panic!("Must return role already granted error"),
+        }
+
+        // Ensure the admin cannot grant or revoke admins.
+        let msg = ExecuteMsg::GrantRole { role: Role::Admin, address: "anyone".to_string(), board: None };
+        match execute_as(deps.as_mut(), "admin", msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+        let msg = ExecuteMsg::RevokeRole { role: Role::Admin, address: "admin".to_string(), board: None };
+        match execute_as(deps.as_mut(), "admin", msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Ensure the admin can set without holding the writer role.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "admin", msg).unwrap();
+
+        // Ensure ListRoleMembers lists explicit grants ordered by address.
+        let msg = QueryMsg::ListRoleMembers { role: Role::Writer, start_after: None, limit: None, board: None };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: RoleMembersResponse = from_binary(&res).unwrap();
+        assert_eq!(value.members, vec![Addr::unchecked("writer_a"), Addr::unchecked("writer_b")]);
+        let msg = QueryMsg::ListRoleMembers { role: Role::Writer, start_after: Some("writer_a".to_string()), limit: None, board: None };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: RoleMembersResponse = from_binary(&res).unwrap();
+        assert_eq!(value.members, vec![Addr::unchecked("writer_b")]);
+
+        // Ensure HasRole accounts for the owner and admins.
+        for (role, address, expected) in &[
+            (Role::Writer, "writer_a", true),
+            (Role::Writer, "admin", true),
+            (Role::Writer, "owner", true),
+            (Role::Admin, "admin", true),
+            (Role::Admin, "writer_a", false),
+            (Role::Registrar, "anyone", false),
+        ] {
+            let msg = QueryMsg::HasRole { role: *role, address: address.to_string(), board: None };
+            let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+            let value: HasRoleResponse = from_binary(&res).unwrap();
+            assert_eq!(value.has_role, *expected);
+        }
+    }
+
+    /// Ensure RemoveAddress deletes the score and every record tied to it.
+    #[test]
+    fn remove_address() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract and add a second address.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        let msg = ExecuteMsg::AddAddress { new_address: "2".to_string(), new_score: 20, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+
+        // Try to remove an address as a non-registrar.
+        let msg = ExecuteMsg::RemoveAddress { address: "2".to_string(), memo: None, board: None };
+        match execute_as(deps.as_mut(), "anyone", msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Remove the address as the owner and ensure an event was emitted.
+        let msg = ExecuteMsg::RemoveAddress { address: "2".to_string(), memo: None, board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        assert_eq!(res.events, vec![Event::new("remove_address").add_attribute("address", "2").add_attribute("score", "20")]);
+
+        // Ensure the score and its secondary records are gone.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("2".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert!(!value.exists);
+        assert_eq!(value.kind, None);
+        assert!(!ENTRY_SEQ.has(&deps.storage, "2"));
+        let top = leaderboard_addresses(deps.as_ref(), QueryMsg::TopScores { start_after: None, limit: None, include_profile: false, board: None });
+        assert_eq!(top, vec!["1"]);
+        assert_eq!(RANK_COUNTS.keys(&deps.storage, None, None, Order::Ascending).count(), 11);
+
+        // Ensure removing it again errors.
+        let msg = ExecuteMsg::RemoveAddress { address: "2".to_string(), memo: None, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::AddressNotFound { address }) => assert_eq!(address, "2"),
+            _ => panic!("Must return address not found error"),
+        }
+
+        // Ensure the address can be added again and ranks as a new entry.
+        let msg = ExecuteMsg::AddAddress { new_address: "2".to_string(), new_score: 10, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let top = leaderboard_addresses(deps.as_ref(), QueryMsg::TopScores { start_after: None, limit: None, include_profile: false, board: None });
+        assert_eq!(top, vec!["1", "2"]);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Rank { address: Some("2".to_string()), board: None, name: None }).unwrap();
+        let value: RankResponse = from_binary(&res).unwrap();
+        assert_eq!(value.rank, 2);
+    }
+
+    /// Ensure Increment and Decrement update scores relative to their current value.
+    #[test]
+    fn increment_and_decrement() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract. Address1's score is 10.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Try to increment as a non-writer.
+        let msg = ExecuteMsg::Increment { address: "1".to_string(), delta: 5, register_missing: false, memo: None, board: None };
+        match execute_as(deps.as_mut(), "anyone", msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Increment by 5, then decrement by 20.
+        let msg = ExecuteMsg::Increment { address: "1".to_string(), delta: 5, register_missing: false, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let msg = ExecuteMsg::Decrement { address: "1".to_string(), delta: 20, register_missing: false, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+
+        // Ensure Address1's score is -5.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("1".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, -5);
+
+        // Ensure an unknown address errors unless asked to register it.
+        let msg = ExecuteMsg::Increment { address: "2".to_string(), delta: 5, register_missing: false, memo: None, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::AddressNotFound { address }) => assert_eq!(address, "2"),
+            _ => panic!("Must return address not found error"),
+        }
+    }
+
+    /// Ensure Increment and Decrement fail with a typed error instead of overflowing.
+    #[test]
+    fn increment_and_decrement_overflow() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract and set Address1's score to the maximum.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: i32::MAX, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+
+        // Try to increment past the maximum.
+        let msg = ExecuteMsg::Increment { address: "1".to_string(), delta: 1, register_missing: false, memo: None, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::ScoreOverflow { address, source }) => {
+                assert_eq!(address, "1");
+                assert_eq!(source, OverflowError::new(OverflowOperation::Add, i32::MAX, 1));
+            }
+            _ => panic!("Must return score overflow error"),
+        }
+
+        // Ensure a delta larger than i32::MAX still works when the result fits.
+        let msg = ExecuteMsg::Decrement { address: "1".to_string(), delta: u32::MAX, register_missing: false, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("1".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, i32::MIN);
+
+        // Try to decrement past the minimum.
+        let msg = ExecuteMsg::Decrement { address: "1".to_string(), delta: 1, register_missing: false, memo: None, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::ScoreOverflow { source, .. }) => assert_eq!(source.operation, OverflowOperation::Sub),
+            _ => panic!("Must return score overflow error"),
+        }
+    }
+
+    /// Ensure Increment can register unknown addresses at the default score.
+    #[test]
+    fn increment_registers_missing_addresses() {
+        // Instantiate the contract with a default score of 100.
+        let (mut deps, info, mut msg) = setup();
+        msg.default_score = 100;
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Try to register through Increment as a writer that is not a registrar.
+        let msg = ExecuteMsg::GrantRole { role: Role::Writer, address: "writer".to_string(), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let msg = ExecuteMsg::Increment { address: "2".to_string(), delta: 5, register_missing: true, memo: None, board: None };
+        match execute_as(deps.as_mut(), "writer", msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Register through Increment as the owner.
+        let msg = ExecuteMsg::Increment { address: "2".to_string(), delta: 5, register_missing: true, memo: None, board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        assert!(res.attributes.contains(&attr("registered", "true")));
+
+        // Ensure Address2 was added with the default score plus the delta.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("2".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value, ScoreFromAddressResponse { address: "2".to_string(), score: 105, exists: true, kind: Some(KeyKind::External), profile: None });
+        let top = leaderboard_addresses(deps.as_ref(), QueryMsg::TopScores { start_after: None, limit: None, include_profile: false, board: None });
+        assert_eq!(top, vec!["2", "1"]);
+    }
+
+    /// Ensure BatchUpdate applies mixed operations in order and reports each result.
+    #[test]
+    fn batch_update_applies_every_operation() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract. Address1's score is 10.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Add Address2, set Address1, increment Address2, then remove Address1.
+        let msg = ExecuteMsg::BatchUpdate { updates: vec![
+            ScoreOp::Add { new_address: "2".to_string(), new_score: 20, memo: None },
+            ScoreOp::Set { address: "1".to_string(), new_score: 11, memo: None },
+            ScoreOp::Increment { address: "2".to_string(), delta: 5, register_missing: false, memo: None },
+            ScoreOp::Remove { address: "1".to_string(), memo: None },
+        ],
+            board: None,};
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        assert!(res.attributes.contains(&attr("count", "4")));
+        assert!(res.attributes.contains(&attr("0.method", "add_address")));
+        assert!(res.attributes.contains(&attr("1.score", "11")));
+        assert!(res.attributes.contains(&attr("2.score", "25")));
+        assert_eq!(res.events.len(), 1);
+
+        // Ensure only Address2 is left, with its incremented score.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ListScores { start_after: None, limit: None, include_profile: false, board: None }).unwrap();
+        let value: ListScoresResponse = from_binary(&res).unwrap();
+        assert_eq!(value.scores, vec![ScoreEntry { address: "2".to_string(), score: 25, kind: KeyKind::External, profile: None }]);
+    }
+
+    /// Ensure BatchUpdate reports which operation failed and enforces the maximum size.
+    #[test]
+    fn batch_update_errors() {
+        // Instantiate the contract with a maximum batch size of 2.
+        let (mut deps, info, mut msg) = setup();
+        msg.max_batch_size = Some(2);
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Try to send a batch that is too large.
+        let set = ScoreOp::Set { address: "1".to_string(), new_score: 11, memo: None };
+        let msg = ExecuteMsg::BatchUpdate { updates: vec![set.clone(), set.clone(), set.clone()], board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::BatchTooLarge { size: 3, max: 2 }) => {}
+            _ => panic!("Must return batch too large error"),
+        }
+
+        // Try to send a batch whose second operation targets a missing address.
+        let missing = ScoreOp::Set { address: "2".to_string(), new_score: 11, memo: None };
+        let msg = ExecuteMsg::BatchUpdate { updates: vec![set.clone(), missing], board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::BatchOperationFailed { index: 1, source }) => match *source {
+                ContractError::AddressNotFound { address } => assert_eq!(address, "2"),
+                _ => panic!("Must wrap address not found error"),
+            },
+            _ => panic!("Must return batch operation failed error"),
+        }
+
+        // Try to send a batch as someone without the needed role.
+        let msg = ExecuteMsg::BatchUpdate { updates: vec![set], board: None };
+        match execute_as(deps.as_mut(), "anyone", msg) {
+            Err(ContractError::BatchOperationFailed { index: 0, source }) => {
+                assert!(matches!(*source, ContractError::Unauthorized {}))
+            }
+            _ => panic!("Must return batch operation failed error"),
+        }
+    }
+
+    /// Ensure every write path respects the score bounds and maximum delta.
+    #[test]
+    fn score_bounds_are_enforced() {
+        // Instantiate the contract with scores between -100 and 100,
+        // changing by at most 50 per update.
+        let (mut deps, info, mut msg) = setup();
+        msg.min_score = Some(-100);
+        msg.max_score = Some(100);
+        msg.max_delta = Some(50);
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Try to set Address1 to i32::MIN.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: i32::MIN, memo: None, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::ScoreOutOfRange { min: -100, max: 100 }) => {}
+            _ => panic!("Must return score out of range error"),
+        }
+
+        // Try to set Address1 further than the maximum delta.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 61, memo: None, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::DeltaTooLarge { delta: 51, max: 50 }) => {}
+            _ => panic!("Must return delta too large error"),
+        }
+
+        // Try to add an address above the maximum.
+        let msg = ExecuteMsg::AddAddress { new_address: "2".to_string(), new_score: 101, memo: None, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::ScoreOutOfRange { .. }) => {}
+            _ => panic!("Must return score out of range error"),
+        }
+
+        // Try to increment and decrement past the bounds and the maximum delta.
+        let msg = ExecuteMsg::Increment { address: "1".to_string(), delta: 51, register_missing: false, memo: None, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::DeltaTooLarge { .. }) => {}
+            _ => panic!("Must return delta too large error"),
+        }
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: -40, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let msg = ExecuteMsg::Decrement { address: "1".to_string(), delta: 61, register_missing: false, memo: None, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::ScoreOutOfRange { .. }) => {}
+            _ => panic!("Must return score out of range error"),
+        }
+
+        // Try to sneak an out of range score through a batch.
+        let msg = ExecuteMsg::BatchUpdate { updates: vec![ScoreOp::Add { new_address: "2".to_string(), new_score: -101, memo: None }], board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::BatchOperationFailed { index: 0, source }) => {
+                assert!(matches!(*source, ContractError::ScoreOutOfRange { .. }))
+            }
+            _ => panic!("Must return batch operation failed error"),
+        }
+
+        // Ensure Address1 kept its last valid score.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress { address: Some("1".to_string()), include_profile: false, board: None, name: None }).unwrap();
+        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
+        assert_eq!(value.score, -40);
+    }
+
+    /// Ensure only admins may update the config, and only to a consistent one.
+    #[test]
+    fn update_config() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Try to update the config as a writer.
+        let msg = ExecuteMsg::GrantRole { role: Role::Writer, address: "writer".to_string(), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let update = ExecuteMsg::UpdateConfig {
+            open_registration: None,
+            default_score: None,
+            max_batch_size: None,
+            min_score: Some(0),
+            max_score: Some(50),
+            max_delta: Some(5),
+            max_history: None,
+            board: None,
+        };
+        match execute_as(deps.as_mut(), "writer", update.clone()) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Update the config as an admin.
+        let msg = ExecuteMsg::GrantRole { role: Role::Admin, address: "admin".to_string(), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let _res = execute_as(deps.as_mut(), "admin", update).unwrap();
+
+        // Ensure GetConfig reports the new bounds and keeps the other fields.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetConfig { board: None }).unwrap();
+        let value: ConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(value, ConfigResponse {
+            allow_external_keys: true,
+            open_registration: false,
+            default_score: 0,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            min_score: 0,
+            max_score: 50,
+            max_delta: 5,
+            max_history: DEFAULT_MAX_HISTORY,
+        });
+
+        // Try to invert the bounds, or move them away from the default score.
+        let msg = ExecuteMsg::UpdateConfig {
+            open_registration: None,
+            default_score: None,
+            max_batch_size: None,
+            min_score: Some(60),
+            max_score: None,
+            max_delta: None,
+            max_history: None,
+            board: None,
+        };
+        match execute_as(deps.as_mut(), "admin", msg) {
+            Err(ContractError::InvalidConfig { .. }) => {}
+            _ => panic!("Must return invalid config error"),
+        }
+        let msg = ExecuteMsg::UpdateConfig {
+            open_registration: None,
+            default_score: None,
+            max_batch_size: None,
+            min_score: Some(1),
+            max_score: None,
+            max_delta: None,
+            max_history: None,
+            board: None,
+        };
+        match execute_as(deps.as_mut(), "admin", msg) {
+            Err(ContractError::InvalidConfig { .. }) => {}
+            _ => panic!("Must return invalid config error"),
+        }
+
+        // Ensure instantiation rejects a first score outside the bounds.
+        let (mut deps, info, mut msg) = setup();
+        msg.max_score = Some(5);
+        match instantiate(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::ScoreOutOfRange { .. }) => {}
+            _ => panic!("Must return score out of range error"),
+        }
+    }
+
+    /// Ensure every write appends a history record that survives removal.
+    #[test]
+    fn score_history_records_every_write() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract. Address1's score is 10.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Set, increment, then remove Address1 at a later block.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21, memo: Some("dispute #4".to_string()), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let msg = ExecuteMsg::Increment { address: "1".to_string(), delta: 4, register_missing: false, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let mut env = mock_env();
+        env.block.height += 10;
+        let msg = ExecuteMsg::RemoveAddress { address: "1".to_string(), memo: None, board: None };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+        // Ensure the history holds all four changes in order.
+        let msg = QueryMsg::ScoreHistory { address: Some("1".to_string()), start_after: None, limit: None, board: None, name: None };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: ScoreHistoryResponse = from_binary(&res).unwrap();
+        let changes: Vec<_> = value.records.iter().map(|r| (r.id, r.old_score, r.new_score)).collect();
+        assert_eq!(changes, vec![(0, None, Some(10)), (1, Some(10), Some(21)), (2, Some(21), Some(25)), (3, Some(25), None)]);
+        assert_eq!(value.records[1].memo, Some("dispute #4".to_string()));
+        assert_eq!(value.records[1].sender, Addr::unchecked("owner"));
+        assert_eq!(value.records[3].height, env.block.height);
+        assert_eq!(value.records[3].time, env.block.time);
+
+        // Ensure the history can be paged through.
+        let msg = QueryMsg::ScoreHistory { address: Some("1".to_string()), start_after: Some(1), limit: Some(1), board: None, name: None };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: ScoreHistoryResponse = from_binary(&res).unwrap();
+        assert_eq!(value.records.len(), 1);
+        assert_eq!(value.records[0].id, 2);
+
+        // Try to attach a memo that is too long.
+        let msg = ExecuteMsg::AddAddress { new_address: "1".to_string(), new_score: 0, memo: Some("x".repeat(257)), board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::MemoTooLong { length: 257, max: 256 }) => {}
+            _ => panic!("Must return memo too long error"),
+        }
+    }
+
+    /// Ensure only the newest max_history records are kept per address.
+    #[test]
+    fn score_history_retention_cap() {
+        // Instantiate the contract keeping 3 records per address.
+        let (mut deps, info, mut msg) = setup();
+        msg.max_history = Some(3);
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Set Address1 five times.
+        for n in 1..6 {
+            let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: n, memo: None, board: None };
+            let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        }
+
+        // Ensure only the last 3 records are kept.
+        let msg = QueryMsg::ScoreHistory { address: Some("1".to_string()), start_after: None, limit: None, board: None, name: None };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: ScoreHistoryResponse = from_binary(&res).unwrap();
+        let ids: Vec<u64> = value.records.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![3, 4, 5]);
+
+        // Lower the cap and ensure the next write drops the excess.
+        let msg = ExecuteMsg::UpdateConfig {
+            open_registration: None,
+            default_score: None,
+            max_batch_size: None,
+            min_score: None,
+            max_score: None,
+            max_delta: None,
+            max_history: Some(1),
+            board: None,
+        };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 6, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let msg = QueryMsg::ScoreHistory { address: Some("1".to_string()), start_after: None, limit: None, board: None, name: None };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: ScoreHistoryResponse = from_binary(&res).unwrap();
+        assert_eq!(value.records.len(), 1);
+        assert_eq!(value.records[0].id, 6);
+    }
+
+    /// Ensure scores and the total can be read back at past block heights.
+    #[test]
+    fn scores_at_height() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract at block 12345. Address1's score is 10.
+        let mut env = mock_env();
+        let start = env.block.height;
+        let res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Add Address2 and set Address1 in the next block.
+        env.block.height = start + 1;
+        let msg = ExecuteMsg::AddAddress { new_address: "2".to_string(), new_score: 20, memo: None, board: None };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 15, memo: None, board: None };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+        // Remove Address1 in the block after that.
+        env.block.height = start + 2;
+        let msg = ExecuteMsg::RemoveAddress { address: "1".to_string(), memo: None, board: None };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+        // Ensure each height reports the scores from the start of its block.
+        let score_at = |deps: Deps, address: &str, height: u64| -> Option<i32> {
+            let msg = QueryMsg::ScoreAtHeight { address: Some(address.to_string()), height, board: None, name: None };
+            let value: ScoreAtHeightResponse = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+            value.score
+        };
+        assert_eq!(score_at(deps.as_ref(), "1", start), None);
+        assert_eq!(score_at(deps.as_ref(), "1", start + 1), Some(10));
+        assert_eq!(score_at(deps.as_ref(), "1", start + 2), Some(15));
+        assert_eq!(score_at(deps.as_ref(), "1", start + 3), None);
+        assert_eq!(score_at(deps.as_ref(), "2", start + 1), None);
+        assert_eq!(score_at(deps.as_ref(), "2", start + 9), Some(20));
+
+        // Ensure the total follows the same heights.
+        let total_at = |deps: Deps, height: u64| -> i64 {
+            let msg = QueryMsg::TotalAtHeight { height, board: None };
+            let value: TotalAtHeightResponse = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+            value.total
+        };
+        assert_eq!(total_at(deps.as_ref(), start), 0);
+        assert_eq!(total_at(deps.as_ref(), start + 1), 10);
+        assert_eq!(total_at(deps.as_ref(), start + 2), 35);
+        assert_eq!(total_at(deps.as_ref(), start + 3), 20);
+    }
+
+    /// Ensure the cw4 queries report member weights, with negative scores as 0.
+    #[test]
+    fn cw4_group_queries() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract. The external key "1" is never a member.
+        let mut env = mock_env();
+        let start = env.block.height;
+        let res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Add Alice with 5 and Bob with -3 in the next block.
+        env.block.height = start + 1;
+        for (address, score) in [("alice", 5), ("bob", -3)].iter() {
+            let msg = ExecuteMsg::AddAddress { new_address: address.to_string(), new_score: *score, memo: None, board: None };
+            let _res = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+        }
+
+        // Ensure Bob is a member with a weight of 0.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ListMembers { start_after: None, limit: None, board: None }).unwrap();
+        let value: MemberListResponse = from_binary(&res).unwrap();
+        assert_eq!(value.members, vec![
+            Member { addr: "alice".to_string(), weight: 5 },
+            Member { addr: "bob".to_string(), weight: 0 },
+        ]);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::TotalWeight { board: None }).unwrap();
+        let value: TotalWeightResponse = from_binary(&res).unwrap();
+        assert_eq!(value.weight, 5);
+
+        // Ensure external keys are not members, and weights can be read at past heights.
+        let member = |deps: Deps, addr: &str, at_height: Option<u64>| -> Option<u64> {
+            let msg = QueryMsg::Member { addr: addr.to_string(), at_height, board: None };
+            let value: MemberResponse = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+            value.weight
+        };
+        assert_eq!(member(deps.as_ref(), "1", None), None);
+        assert_eq!(member(deps.as_ref(), "alice", Some(start + 1)), None);
+        assert_eq!(member(deps.as_ref(), "alice", Some(start + 2)), Some(5));
+
+        // Ensure the cw4 admin is the owner.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Admin { board: None }).unwrap();
+        let value: AdminResponse = from_binary(&res).unwrap();
+        assert_eq!(value.admin, Some("owner".to_string()));
+    }
+
+    /// Ensure hooks get a MemberChangedHookMsg whenever a member weight changes.
+    #[test]
+    fn cw4_member_changed_hooks() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract and add Alice with a score of 5.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        let msg = ExecuteMsg::AddAddress { new_address: "alice".to_string(), new_score: 5, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+
+        // Try to add a hook as a writer, then add it as the owner.
+        let msg = ExecuteMsg::GrantRole { role: Role::Writer, address: "writer".to_string(), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        match execute_as(deps.as_mut(), "writer", ExecuteMsg::AddHook { addr: "hook".to_string(), kind: HookKind::Member, board: None }) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+        let _res = execute_as(deps.as_mut(), "owner", ExecuteMsg::AddHook { addr: "hook".to_string(), kind: HookKind::Member, board: None }).unwrap();
+        match execute_as(deps.as_mut(), "owner", ExecuteMsg::AddHook { addr: "hook".to_string(), kind: HookKind::Member, board: None }) {
+            Err(ContractError::HookAlreadyRegistered { address }) => assert_eq!(address, "hook"),
+            _ => panic!("Must return hook already registered error"),
+        }
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Hooks { board: None }).unwrap();
+        let value: HooksResponse = from_binary(&res).unwrap();
+        assert_eq!(value.hooks, vec!["hook".to_string()]);
+
+        // Ensure lowering Alice below zero notifies the hook of a weight of 0.
+        let msg = ExecuteMsg::Set { address: "alice".to_string(), new_score: -2, memo: None, board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let expected = MemberChangedHookMsg::one(MemberDiff::new("alice", Some(5), Some(0)));
+        assert_eq!(res.messages, vec![SubMsg::new(expected.into_cosmos_msg("hook").unwrap())]);
+
+        // Ensure changes that leave the weight at 0, or touch external keys, send nothing.
+        let msg = ExecuteMsg::Set { address: "alice".to_string(), new_score: -7, memo: None, board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        assert_eq!(res.messages.len(), 0);
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 70, memo: None, board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        assert_eq!(res.messages.len(), 0);
+
+        // Ensure adding and removing members notifies the hook.
+        let msg = ExecuteMsg::AddAddress { new_address: "bob".to_string(), new_score: 3, memo: None, board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let expected = MemberChangedHookMsg::one(MemberDiff::new("bob", None, Some(3)));
+        assert_eq!(res.messages, vec![SubMsg::new(expected.into_cosmos_msg("hook").unwrap())]);
+        let msg = ExecuteMsg::RemoveAddress { address: "bob".to_string(), memo: None, board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let expected = MemberChangedHookMsg::one(MemberDiff::new("bob", Some(3), None));
+        assert_eq!(res.messages, vec![SubMsg::new(expected.into_cosmos_msg("hook").unwrap())]);
+
+        // Remove the hook and ensure it is no longer notified.
+        let _res = execute_as(deps.as_mut(), "owner", ExecuteMsg::RemoveHook { addr: "hook".to_string(), kind: HookKind::Member, board: None }).unwrap();
+        let msg = ExecuteMsg::Set { address: "alice".to_string(), new_score: 9, memo: None, board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        assert_eq!(res.messages.len(), 0);
+        match execute_as(deps.as_mut(), "owner", ExecuteMsg::RemoveHook { addr: "hook".to_string(), kind: HookKind::Member, board: None }) {
+            Err(ContractError::HookNotRegistered { address }) => assert_eq!(address, "hook"),
+            _ => panic!("Must return hook not registered error"),
+        }
+    }
+
+    /// Ensure Score hooks get the old and new score of every write.
+    #[test]
+    fn score_changed_hooks() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract and add a Score hook and a Member hook.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        let msg = ExecuteMsg::AddHook { addr: "scores".to_string(), kind: HookKind::Score, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let msg = ExecuteMsg::AddHook { addr: "group".to_string(), kind: HookKind::Member, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+
+        // Ensure ListHooks reports both, and Hooks only the Member hook.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ListHooks { board: None }).unwrap();
+        let value: ListHooksResponse = from_binary(&res).unwrap();
+        assert_eq!(value.hooks, vec![
+            HookEntry { addr: Addr::unchecked("group"), kind: HookKind::Member },
+            HookEntry { addr: Addr::unchecked("scores"), kind: HookKind::Score },
+        ]);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Hooks { board: None }).unwrap();
+        let value: HooksResponse = from_binary(&res).unwrap();
+        assert_eq!(value.hooks, vec!["group".to_string()]);
+
+        // Ensure every kind of write notifies the Score hook.
+        let score_msg = |old_score, new_score| {
+            let msg = ScoreChangedHookMsg { address: "1".to_string(), old_score, new_score };
+            SubMsg::new(msg.into_cosmos_msg("scores").unwrap())
+        };
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 11, memo: None, board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        assert_eq!(res.messages, vec![score_msg(Some(10), Some(11))]);
+        let msg = ExecuteMsg::Decrement { address: "1".to_string(), delta: 20, register_missing: false, memo: None, board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        assert_eq!(res.messages, vec![score_msg(Some(11), Some(-9))]);
+        let msg = ExecuteMsg::RemoveAddress { address: "1".to_string(), memo: None, board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        assert_eq!(res.messages, vec![score_msg(Some(-9), None)]);
+        let msg = ExecuteMsg::BatchUpdate { updates: vec![ScoreOp::Add { new_address: "1".to_string(), new_score: 3, memo: None }], board: None };
+        let res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        assert_eq!(res.messages, vec![score_msg(None, Some(3))]);
+
+        // Ensure the message reaches hook contracts in the documented shape.
+        let msg = ScoreChangedHookMsg { address: "1".to_string(), old_score: None, new_score: Some(3) };
+        assert_eq!(
+            msg.into_binary().unwrap(),
+            Binary::from(br#"{"score_changed_hook":{"address":"1","old_score":null,"new_score":3}}"#.to_vec())
+        );
+
+        // Remove the Score hook and ensure the Member hook is kept.
+        let msg = ExecuteMsg::RemoveHook { addr: "scores".to_string(), kind: HookKind::Score, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ListHooks { board: None }).unwrap();
+        let value: ListHooksResponse = from_binary(&res).unwrap();
+        assert_eq!(value.hooks, vec![HookEntry { addr: Addr::unchecked("group"), kind: HookKind::Member }]);
+    }
+
+    /// Ensure pausing blocks every message except Unpause and ownership recovery.
+    #[test]
+    fn pause_and_unpause() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
+
+        // Instantiate the contract and grant the admin and writer roles.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        let msg = ExecuteMsg::GrantRole { role: Role::Admin, address: "admin".to_string(), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let msg = ExecuteMsg::GrantRole { role: Role::Writer, address: "writer".to_string(), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+
+        // Try to pause as a writer, then pause as an admin.
+        let pause = ExecuteMsg::Pause { reason: "bad import".to_string(), board: None };
+        match execute_as(deps.as_mut(), "writer", pause.clone()) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+        let _res = execute_as(deps.as_mut(), "admin", pause.clone()).unwrap();
+
+        // Ensure PauseStatus reports who paused the contract, when and why.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PauseStatus { board: None }).unwrap();
+        let value: PauseStatusResponse = from_binary(&res).unwrap();
+        assert_eq!(value, PauseStatusResponse {
+            paused: true,
+            pause: Some(PauseInfo {
+                paused_by: Addr::unchecked("admin"),
+                height: mock_env().block.height,
+                time: mock_env().block.time,
+                reason: "bad import".to_string(),
+            }),
+        });
+
+        // Try to write, manage roles and pause again while paused.
+        let blocked = vec![
+            ExecuteMsg::Set { address: "1".to_string(), new_score: 21, memo: None, board: None },
+            ExecuteMsg::AddAddress { new_address: "2".to_string(), new_score: 20, memo: None, board: None },
+            ExecuteMsg::BatchUpdate { updates: vec![], board: None },
+            ExecuteMsg::GrantRole { role: Role::Writer, address: "other".to_string(), board: None },
+            ExecuteMsg::RegisterName { name: "owner".to_string() },
+            ExecuteMsg::CreateBoard { board: "weekly".to_string(), msg: setup().2 },
+            ExecuteMsg::CloseBoard { board: "weekly".to_string() },
+            pause,
+        ];
+        for msg in blocked {
+            match execute_as(deps.as_mut(), "owner", msg) {
+                Err(ContractError::ContractPaused { reason }) => assert_eq!(reason, "bad import"),
+                _ => panic!("Must return contract paused error"),
+            }
+        }
 
-    /// A "DO BEFORE EACH" testing utility function.
-    /// Returns the parameters necessary to instantiate a smart contract.
-    pub fn setup() -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, MessageInfo, InstantiateMsg) {
-        // Mock out dependencies.
-        let deps = mock_dependencies(&[]);
+        // Ensure ownership can still be recovered while paused.
+        let msg = ExecuteMsg::ProposeOwner { new_owner: "new_owner".to_string(), expiry: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let _res = execute_as(deps.as_mut(), "new_owner", ExecuteMsg::AcceptOwnership { board: None }).unwrap();
 
-        // Mock out account info.
-        let info = mock_info("owner", &coins(1000, "earth"));
+        // Try to unpause as a writer, then unpause as the new owner.
+        match execute_as(deps.as_mut(), "writer", ExecuteMsg::Unpause { board: None }) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+        let _res = execute_as(deps.as_mut(), "new_owner", ExecuteMsg::Unpause { board: None }).unwrap();
+        match execute_as(deps.as_mut(), "new_owner", ExecuteMsg::Unpause { board: None }) {
+            Err(ContractError::NotPaused {}) => {}
+            _ => panic!("Must return not paused error"),
+        }
 
-        // Create a message that could be used to instantiate a smart contract.
-        let msg = InstantiateMsg {
-            first_address: "1".to_string(),
-            first_address_score: 10 as i32
-        };
+        // Ensure writes work again.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "writer", msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PauseStatus { board: None }).unwrap();
+        let value: PauseStatusResponse = from_binary(&res).unwrap();
+        assert_eq!(value, PauseStatusResponse { paused: false, pause: None });
+    }
 
-        // Return all three to be used in test cases.
-        return (deps, info, msg);
+    /// Recompute the state digest from scratch, the way an off-chain system would.
+    fn digest_of(table: &[(&str, i32)]) -> String {
+        // Hash every pair and sort the hashes by path.
+        let mut leaves: Vec<(Vec<u8>, Vec<u8>)> = table
+            .iter()
+            .map(|(address, score)| {
+                let path = Sha256::digest(address.as_bytes()).to_vec();
+                let hash = Sha256::new().chain([0u8]).chain(score.to_be_bytes()).chain(address.as_bytes()).finalize();
+                (path, hash.to_vec())
+            })
+            .collect();
+        leaves.sort();
+        let root = if leaves.is_empty() { vec![0u8; 32] } else { subtree_hash(&leaves) };
+        root.iter().map(|byte| format!("{:02x}", byte)).collect()
     }
 
-    // ===========================
-    // VERBOSE REQUIREMENT TESTS
-    // ===========================
+    /// Hash (path, hash) leaves sorted by path into a Merkle Patricia trie.
+    fn subtree_hash(leaves: &[(Vec<u8>, Vec<u8>)]) -> Vec<u8> {
+        if leaves.len() == 1 {
+            return leaves[0].1.clone();
+        }
 
-    /// Test instantiating the contract and setting the owner.
+        // Split on the first bit where the first and last paths differ.
+        let is_set = |path: &[u8], bit: u16| path[usize::from(bit / 8)] & (0x80 >> (bit % 8)) != 0;
+        let (first, last) = (&leaves[0].0, &leaves[leaves.len() - 1].0);
+        let bit = (0..256u16).find(|bit| is_set(first, *bit) != is_set(last, *bit)).unwrap();
+        let split = leaves.iter().position(|(path, _)| is_set(path, bit)).unwrap();
+        Sha256::new()
+            .chain([1u8])
+            .chain(bit.to_be_bytes())
+            .chain(subtree_hash(&leaves[..split]))
+            .chain(subtree_hash(&leaves[split..]))
+            .finalize()
+            .to_vec()
+    }
+
+    /// Query the state digest.
+    fn state_digest(deps: Deps) -> String {
+        let value: StateDigestResponse = from_binary(&query(deps, mock_env(), QueryMsg::StateDigest { board: None }).unwrap()).unwrap();
+        value.digest
+    }
+
+    /// Ensure the state digest tracks every write and only depends on the table.
     #[test]
-    fn instantiate_contract_and_set_owner() {
+    fn state_digest_commits_to_the_table() {
         // Call the "Do Before Each" testing utility function.
         let (mut deps, info, msg) = setup();
 
-        // Instantiate the contract.
-        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        // Instantiate the contract. Address1's score is 10.
+        let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(state_digest(deps.as_ref()), digest_of(&[("1", 10)]));
 
-        // Ensure instantiation was successful.
+        // Add Address2 and Address3, update Address1, then remove Address3.
+        let msgs = vec![
+            ExecuteMsg::AddAddress { new_address: "2".to_string(), new_score: -20, memo: None, board: None },
+            ExecuteMsg::AddAddress { new_address: "3".to_string(), new_score: 30, memo: None, board: None },
+            ExecuteMsg::Set { address: "1".to_string(), new_score: 11, memo: None, board: None },
+            ExecuteMsg::RemoveAddress { address: "3".to_string(), memo: None, board: None },
+        ];
+        for msg in msgs {
+            let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        }
+        let digest = state_digest(deps.as_ref());
+        assert_eq!(digest, digest_of(&[("1", 11), ("2", -20)]));
+
+        // Ensure the same table reached another way has the same digest.
+        let (mut other, _, _) = setup();
+        let res = instantiate(other.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
+        let msg = ExecuteMsg::AddAddress { new_address: "2".to_string(), new_score: -20, memo: None, board: None };
+        let _res = execute_as(other.as_mut(), "owner", msg).unwrap();
+        let msg = ExecuteMsg::Increment { address: "1".to_string(), delta: 1, register_missing: false, memo: None, board: None };
+        let _res = execute_as(other.as_mut(), "owner", msg).unwrap();
+        assert_eq!(state_digest(other.as_ref()), digest);
+
+        // Ensure removing every address brings the digest back to zero.
+        for address in ["1", "2"].iter() {
+            let msg = ExecuteMsg::RemoveAddress { address: address.to_string(), memo: None, board: None };
+            let _res = execute_as(other.as_mut(), "owner", msg).unwrap();
+        }
+        assert_eq!(state_digest(other.as_ref()), "0".repeat(64));
+        assert_eq!(DIGEST_NODES.keys(&other.storage, None, None, Order::Ascending).count(), 0);
     }
 
-    /// Test making a read query to get the owner of the smart contract.
+    /// Ensure the digest trie matches a rebuild from scratch through many
+    /// inserts, updates and removals.
     #[test]
-    fn support_a_read_query_to_get_the_owner_of_the_start_contract() {
+    fn state_digest_follows_many_writes() {
         // Call the "Do Before Each" testing utility function.
         let (mut deps, info, msg) = setup();
 
-        // Instantiate the contract. 
+        // Instantiate the contract. Address1's score is 10.
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
 
-        // Ensure the read query returns the owner of the smart contract. 
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwner {}).unwrap();
-        let value: OwnerResponse = from_binary(&res).unwrap();
-        assert_eq!("owner", value.owner);
+        // Add 40 addresses, update every other one and remove every third.
+        let mut table: BTreeMap<String, i32> = BTreeMap::new();
+        table.insert("1".to_string(), 10);
+        for index in 2..42 {
+            let msg = ExecuteMsg::AddAddress { new_address: index.to_string(), new_score: index, memo: None, board: None };
+            let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+            table.insert(index.to_string(), index);
+        }
+        for index in (2..42).step_by(2) {
+            let msg = ExecuteMsg::Set { address: index.to_string(), new_score: -index, memo: None, board: None };
+            let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+            table.insert(index.to_string(), -index);
+        }
+        for index in (2..42).step_by(3) {
+            let msg = ExecuteMsg::RemoveAddress { address: index.to_string(), memo: None, board: None };
+            let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+            table.remove(&index.to_string());
+        }
+        let pairs: Vec<(&str, i32)> = table.iter().map(|(address, score)| (address.as_str(), *score)).collect();
+        assert_eq!(state_digest(deps.as_ref()), digest_of(&pairs));
+
+        // Ensure the trie keeps one branch per pair but the first.
+        assert_eq!(DIGEST_NODES.keys(&deps.storage, None, None, Order::Ascending).count(), pairs.len() - 1);
     }
 
-    /// Testing storing scores for different addresses in the smart contract state.
-    /// (ex. {address_1: 10, address_2: 20})
+    /// Ensure GetHash lists the table with keys in sorted order.
     #[test]
-    fn store_the_score_for_different_addresses_in_the_smart_contract_state() {
+    fn get_hash_is_sorted() {
         // Call the "Do Before Each" testing utility function.
         let (mut deps, info, msg) = setup();
 
-        // Instantiate the contract.
+        // Instantiate the contract and add addresses out of order.
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
+        for (address, score) in [("9", 90), ("10", 100), ("2", 20)].iter() {
+            let msg = ExecuteMsg::AddAddress { new_address: address.to_string(), new_score: *score, memo: None, board: None };
+            let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        }
 
-        // Call AddAddress.
-        let info = mock_info("owner", &coins(1000, "earth"));
-        let new_address = "2".to_string();
-        let new_score = 20 as i32;
-        let msg = ExecuteMsg::AddAddress { new_address: new_address, new_score: new_score};
-        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-
-        // Ensure Address1's score is 10.
-        let address = "1".to_string();
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress {address : address}).unwrap();
-        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
-        assert_eq!(value.score, 10 as i32);
-
-        // Ensure Address2's score is 20.
-        let address = "2".to_string();
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress {address : address}).unwrap();
-        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
-        assert_eq!(value.score, 20 as i32);
+        // Ensure the JSON String has its keys in sorted order.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetHash { board: None }).unwrap();
+        let value: HashResponse = from_binary(&res).unwrap();
+        assert_eq!(value.hash, r#"{"1":10,"10":100,"2":20,"9":90}"#);
     }
 
-    /// Testing support for an execute message where only the owner
-    /// of the smart contract can set the score of an address.
-    /// Case: Owner.
+    /// Ensure the contract can start with a full score table and an explicit owner.
     #[test]
-    fn set_by_owner() {
+    fn instantiate_with_initial_scores_and_owner() {
         // Call the "Do Before Each" testing utility function.
-        let (mut deps, info, msg) = setup();
+        let (mut deps, info, mut msg) = setup();
 
-        // Instantiate the contract.
+        // Instantiate the contract on behalf of alice with three scores.
+        msg.first_address = None;
+        msg.initial_scores = vec![("1".to_string(), 10), ("2".to_string(), -20), ("bob".to_string(), 30)];
+        msg.owner = Some("alice".to_string());
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
 
-        // Execute Set as owner.
-        let info = mock_info("owner", &coins(1000, "earth"));
-        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21 as i32};
-        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        // Ensure alice owns the contract, not the sender.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetOwner { board: None }).unwrap();
+        let value: OwnerResponse = from_binary(&res).unwrap();
+        assert_eq!(Some(Addr::unchecked("alice")), value.owner);
 
-        // Make sure Address1's score is 21.
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress {address : "1".to_string()}).unwrap();
-        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
-        assert_eq!(value.score, 21 as i32);
+        // Ensure every score was stored.
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetHash { board: None }).unwrap();
+        let value: HashResponse = from_binary(&res).unwrap();
+        assert_eq!(value.hash, r#"{"1":10,"2":-20,"bob":30}"#);
+        assert_eq!(state_digest(deps.as_ref()), digest_of(&[("1", 10), ("2", -20), ("bob", 30)]));
     }
 
-    /// Testing support for an execute message where only the owner
-    /// of the smart contract can set the score of an address.
-    /// Case: Non-owner.
+    /// Ensure duplicate initial addresses are all named and nothing is saved.
     #[test]
-    fn set_by_anyone() {
+    fn instantiate_rejects_duplicate_addresses() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, mut msg) = setup();
+
+        // Instantiate with "1" repeated from first_address and "2" repeated in the table.
+        msg.initial_scores = vec![
+            ("2".to_string(), 20),
+            ("1".to_string(), 11),
+            ("2".to_string(), 21),
+            ("3".to_string(), 30),
+        ];
+        match instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()) {
+            Err(ContractError::DuplicateAddresses { addresses }) => {
+                assert_eq!(addresses, vec!["1".to_string(), "2".to_string()]);
+            }
+            _ => panic!("Must return duplicate addresses error"),
+        }
+
+        // Ensure an invalid score anywhere in the table also fails up front.
+        msg.initial_scores = vec![("2".to_string(), 20), ("3".to_string(), 300)];
+        msg.max_score = Some(100);
+        match instantiate(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::ScoreOutOfRange { .. }) => {}
+            _ => panic!("Must return score out of range error"),
+        }
+
+        // Ensure nothing was saved.
+        assert!(CONFIG.may_load(&deps.storage).unwrap().is_none());
+        assert!(STATE.may_load(&deps.storage).unwrap().is_none());
+        assert!(SCORES.may_load(&deps.storage, "1").unwrap().is_none());
+        assert!(SCORES.may_load(&deps.storage, "2").unwrap().is_none());
+    }
+
+    /// Build a profile with a display name and a single tag.
+    fn profile_of(display_name: &str, tag: &str) -> Profile {
+        let tags = vec![("team".to_string(), tag.to_string())];
+        Profile { display_name: Some(display_name.to_string()), uri: None, tags }
+    }
+
+    /// Ensure an address can set its own profile, admins can set any, and nobody else can.
+    #[test]
+    fn set_profile_by_self_or_admin() {
         // Call the "Do Before Each" testing utility function.
         let (mut deps, info, msg) = setup();
 
-        // Instantiate the contract.
+        // Instantiate the contract and add alice and bob.
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
+        for address in ["alice", "bob"].iter() {
+            let msg = ExecuteMsg::AddAddress { new_address: address.to_string(), new_score: 5, memo: None, board: None };
+            let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        }
 
-        // Execute Set as a non-owner.
-        let info = mock_info("anyone", &coins(2, "token"));
-        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 21 as i32};
-        let res = execute(deps.as_mut(), mock_env(), info, msg);
-        
-        // Check if the program errors.
-        match res {
+        // Ensure alice can set her own profile but not bob's.
+        let msg = ExecuteMsg::SetProfile { address: None, profile: profile_of("Alice", "red"), board: None };
+        let _res = execute_as(deps.as_mut(), "alice", msg).unwrap();
+        let msg = ExecuteMsg::SetProfile { address: Some("bob".to_string()), profile: profile_of("Bob", "red"), board: None };
+        match execute_as(deps.as_mut(), "alice", msg.clone()) {
             Err(ContractError::Unauthorized {}) => {}
             _ => panic!("Must return unauthorized error"),
         }
 
-        // Ensure Address1's score is still 10.
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress {address : "1".to_string()}).unwrap();
-        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
-        assert_eq!(value.score, 10 as i32);
+        // Ensure the owner can set bob's profile and the profile of an external key.
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let msg = ExecuteMsg::SetProfile { address: Some("1".to_string()), profile: profile_of("One", "blue"), board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+
+        // Ensure addresses that are not stored cannot have a profile.
+        let msg = ExecuteMsg::SetProfile { address: None, profile: profile_of("Carol", "red"), board: None };
+        match execute_as(deps.as_mut(), "carol", msg) {
+            Err(ContractError::AddressNotFound { .. }) => {}
+            _ => panic!("Must return address not found error"),
+        }
+
+        // Ensure the profile is only returned on request.
+        let msg = QueryMsg::GetScoreFromAddress { address: Some("alice".to_string()), include_profile: false, board: None, name: None };
+        let value: ScoreFromAddressResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.profile, None);
+        let msg = QueryMsg::GetScoreFromAddress { address: Some("alice".to_string()), include_profile: true, board: None, name: None };
+        let value: ScoreFromAddressResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.profile, Some(profile_of("Alice", "red")));
+
+        // Ensure the list queries include every profile on request.
+        let msg = QueryMsg::ListScores { start_after: None, limit: None, include_profile: true, board: None };
+        let value: ListScoresResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        let names: Vec<_> = value.scores.iter().map(|entry| entry.profile.clone().and_then(|p| p.display_name)).collect();
+        assert_eq!(names, vec![Some("One".to_string()), Some("Alice".to_string()), Some("Bob".to_string())]);
+        let msg = QueryMsg::TopScores { start_after: None, limit: Some(1), include_profile: true, board: None };
+        let value: ListScoresResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.scores[0].profile, Some(profile_of("One", "blue")));
+
+        // Ensure an empty profile or removing the address clears the profile.
+        let msg = ExecuteMsg::SetProfile { address: None, profile: Profile::default(), board: None };
+        let _res = execute_as(deps.as_mut(), "alice", msg).unwrap();
+        assert!(PROFILES.may_load(&deps.storage, "alice").unwrap().is_none());
+        let msg = ExecuteMsg::RemoveAddress { address: "bob".to_string(), memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        assert!(PROFILES.may_load(&deps.storage, "bob").unwrap().is_none());
     }
 
-    /// Test support for a read query to get the score for a particular address.
+    /// Ensure profiles above the size limits are rejected.
     #[test]
-    fn read_query_to_get_the_score_of_particular_address() {
+    fn set_profile_enforces_size_limits() {
         // Call the "Do Before Each" testing utility function.
         let (mut deps, info, msg) = setup();
 
@@ -347,187 +4214,319 @@ mod tests {
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
 
-        // EnsEnsure Address1's score is still 10.
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress {address : "1".to_string()}).unwrap();
-        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
-        assert_eq!(value.score, 10 as i32);
-    }
+        // Ensure a long display name is rejected.
+        let profile = profile_of(&"a".repeat(65), "red");
+        let msg = ExecuteMsg::SetProfile { address: Some("1".to_string()), profile, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::ProfileFieldTooLong { field, length: 65, max: 64 }) => assert_eq!(field, "display_name"),
+            _ => panic!("Must return profile field too long error"),
+        }
 
-    // ===========================
-    // UNIT TESTS
-    // ===========================
+        // Ensure a long tag value is rejected.
+        let profile = profile_of("One", &"a".repeat(129));
+        let msg = ExecuteMsg::SetProfile { address: Some("1".to_string()), profile, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::ProfileFieldTooLong { field, .. }) => assert_eq!(field, "tag team"),
+            _ => panic!("Must return profile field too long error"),
+        }
 
-    /// Ensure one cannot set at an invalid address.
+        // Ensure too many tags are rejected.
+        let mut profile = Profile::default();
+        for i in 0..17 {
+            profile.tags.push((i.to_string(), "x".to_string()));
+        }
+        let msg = ExecuteMsg::SetProfile { address: Some("1".to_string()), profile, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::TooManyProfileTags { count: 17, max: 16 }) => {}
+            _ => panic!("Must return too many profile tags error"),
+        }
+
+        // Ensure a tag key may only appear once.
+        let mut profile = profile_of("One", "red");
+        profile.tags.push(("team".to_string(), "blue".to_string()));
+        let msg = ExecuteMsg::SetProfile { address: Some("1".to_string()), profile, board: None };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::DuplicateProfileTag { key }) => assert_eq!(key, "team"),
+            _ => panic!("Must return duplicate profile tag error"),
+        }
+    }
+
+    /// Ensure names are normalized, unique, and resolve in both directions.
     #[test]
-    fn set_by_owner_at_invalid_address() {
+    fn register_and_resolve_names() {
         // Call the "Do Before Each" testing utility function.
         let (mut deps, info, msg) = setup();
 
-        // Instantiate the contract.
+        // Instantiate the contract and add alice.
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
+        let msg = ExecuteMsg::AddAddress { new_address: "alice".to_string(), new_score: 42, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
 
-        // Execute Set at an address that is not in our state HashMap.
-        let info = mock_info("owner", &coins(1000, "earth"));
-        let msg = ExecuteMsg::Set { address: "2".to_string(), new_score: 21 as i32};
-        let res = execute(deps.as_mut(), mock_env(), info, msg);
-        
-        // Check if the program errors.
-        match res {
-            Err(ContractError::Unauthorized {}) => {}
-            _ => panic!("Must provide a valid address to set."),
+        // Register a name for alice, which is normalized.
+        let msg = ExecuteMsg::RegisterName { name: " Ally_1 ".to_string() };
+        let res = execute_as(deps.as_mut(), "alice", msg).unwrap();
+        assert_eq!(res.attributes[1], attr("name", "ally_1"));
+
+        // Ensure bob cannot take the name in any case.
+        let msg = ExecuteMsg::RegisterName { name: "ALLY_1".to_string() };
+        match execute_as(deps.as_mut(), "bob", msg) {
+            Err(ContractError::NameTaken { name }) => assert_eq!(name, "ally_1"),
+            _ => panic!("Must return name taken error"),
+        }
+
+        // Ensure invalid names are rejected.
+        for name in ["al", &"a".repeat(33), "1ally", "ally!", "terra1 ally"].iter() {
+            let msg = ExecuteMsg::RegisterName { name: name.to_string() };
+            match execute_as(deps.as_mut(), "bob", msg) {
+                Err(ContractError::InvalidName { .. }) => {}
+                _ => panic!("Must return invalid name error"),
+            }
         }
+
+        // Ensure the name resolves both ways.
+        let msg = QueryMsg::ResolveName { name: "Ally_1".to_string() };
+        let value: ResolveNameResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value, ResolveNameResponse { name: "ally_1".to_string(), address: Some(Addr::unchecked("alice")) });
+        let msg = QueryMsg::ReverseLookup { address: "alice".to_string() };
+        let value: ReverseLookupResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.name, Some("ally_1".to_string()));
+
+        // Ensure score queries accept the name and report the address it resolved to.
+        let msg = QueryMsg::GetScoreFromAddress { address: None, include_profile: false, board: None, name: Some("Ally_1".to_string()) };
+        let value: ScoreFromAddressResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!((value.address.as_str(), value.score, value.exists), ("alice", 42, true));
+        let msg = QueryMsg::Rank { address: None, board: None, name: Some("ally_1".to_string()) };
+        let value: RankResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.address, "alice");
+
+        // Ensure registering a new name releases the old one.
+        let msg = ExecuteMsg::RegisterName { name: "alice-2".to_string() };
+        let _res = execute_as(deps.as_mut(), "alice", msg).unwrap();
+        let msg = QueryMsg::ResolveName { name: "ally_1".to_string() };
+        let value: ResolveNameResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.address, None);
+        let msg = ExecuteMsg::RegisterName { name: "ally_1".to_string() };
+        let _res = execute_as(deps.as_mut(), "bob", msg).unwrap();
     }
 
-    /// Ensure one cannot add an address if it already exists.
+    /// Ensure an address is never read as a name, and a query takes exactly one of them.
     #[test]
-    fn error_if_adding_to_existing_address() {
+    fn addresses_are_never_read_as_names() {
         // Call the "Do Before Each" testing utility function.
         let (mut deps, info, msg) = setup();
 
-        // Instantiate the contract.
+        // Instantiate the contract and add alice and bob.
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
+        for (address, score) in [("alice", 1), ("bob", 2)].iter() {
+            let msg = ExecuteMsg::AddAddress { new_address: address.to_string(), new_score: *score, memo: None, board: None };
+            let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        }
 
-        // Call AddAddress with an invalid address.
-        let info = mock_info("owner", &coins(1000, "earth"));
-        let new_address = "1".to_string();
-        let new_score = 20 as i32;
-        let msg = ExecuteMsg::AddAddress { new_address: new_address, new_score: new_score};
-        let res = execute(deps.as_mut(), mock_env(), info, msg);
-
-        // Check if the program errors.
-        match res {
-            Err(ContractError::Unauthorized {}) => {}
-            _ => panic!("Must return unauthorized error"),
+        // Let alice register the name "bob", and carol the unused identifier "player42".
+        for (sender, name) in [("alice", "bob"), ("carol", "player42")].iter() {
+            let _res = execute_as(deps.as_mut(), sender, ExecuteMsg::RegisterName { name: name.to_string() }).unwrap();
         }
+
+        // Ensure "bob" as an address still refers to bob's score, and as a name to alice's.
+        let msg = QueryMsg::GetScoreFromAddress { address: Some("bob".to_string()), include_profile: false, board: None, name: None };
+        let value: ScoreFromAddressResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!((value.address.as_str(), value.score), ("bob", 2));
+        let msg = QueryMsg::GetScoreFromAddress { address: None, include_profile: false, board: None, name: Some("bob".to_string()) };
+        let value: ScoreFromAddressResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!((value.address.as_str(), value.score), ("alice", 1));
+
+        // Ensure the unused identifier "player42" is not stored, whatever its name says.
+        let msg = QueryMsg::GetScoreFromAddress { address: Some("player42".to_string()), include_profile: false, board: None, name: None };
+        let value: ScoreFromAddressResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!((value.address.as_str(), value.exists, value.kind), ("player42", false, None));
+
+        // Ensure a query with both or neither of address and name fails.
+        let msg = QueryMsg::Rank { address: Some("bob".to_string()), board: None, name: Some("bob".to_string()) };
+        assert!(query(deps.as_ref(), mock_env(), msg).is_err());
+        let msg = QueryMsg::Rank { address: None, board: None, name: None };
+        assert!(query(deps.as_ref(), mock_env(), msg).is_err());
     }
 
-    /// Testing storing scores for lots of addresses in the smart contract state.
+    /// Create a board owned by owner, with a single score at "1".
+    fn create_board(deps: DepsMut, board: &str, owner: &str, score: i32) -> Result<Response, ContractError> {
+        let (_, _, mut msg) = setup();
+        msg.first_address_score = score;
+        msg.owner = Some(owner.to_string());
+        execute_as(deps, "owner", ExecuteMsg::CreateBoard { board: board.to_string(), msg })
+    }
+
+    /// Ensure boards keep their own owners, config and entries, the way cross_set
+    /// needs two contracts for.
     #[test]
-    fn lots_of_addresses() {
+    fn boards_are_independent() {
         // Call the "Do Before Each" testing utility function.
         let (mut deps, info, msg) = setup();
 
-        // Instantiate the contract.
+        // Instantiate the contract and create a board owned by bob.
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
+        let res = create_board(deps.as_mut(), "Weekly", "bob", 17).unwrap();
+        assert_eq!(res.attributes[1], attr("board", "weekly"));
 
-        for n in 2..47 {
-            // Call AddAddress.
-            let info = mock_info("owner", &coins(1000, "earth"));
-            let new_address = n.to_string();
-            let new_score = n * 10 as i32;
-            let msg = ExecuteMsg::AddAddress { new_address: new_address, new_score: new_score};
-            let _res = execute(deps.as_mut(), mock_env(), info, msg);
+        // Ensure only admins of the default board can create boards, and names are unique.
+        let (_, _, msg) = setup();
+        match execute_as(deps.as_mut(), "bob", ExecuteMsg::CreateBoard { board: "daily".to_string(), msg }) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+        match create_board(deps.as_mut(), "weekly", "bob", 0) {
+            Err(ContractError::BoardAlreadyExists { board }) => assert_eq!(board, "weekly"),
+            _ => panic!("Must return board already exists error"),
         }
 
-        for n in 1..47 {
-            // Ensure Address{n}'s score is n * 10.
-            let address = n.to_string();
-            let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress {address : address}).unwrap();
-            let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
-            assert_eq!(value.score, n * 10 as i32);
+        // Ensure each owner can only set scores on their own board.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 18, memo: None, board: Some("weekly".to_string()) };
+        let res = execute_as(deps.as_mut(), "bob", msg).unwrap();
+        assert_eq!(res.attributes.last(), Some(&attr("board", "weekly")));
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 11, memo: None, board: None };
+        match execute_as(deps.as_mut(), "bob", msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 11, memo: None, board: Some("weekly".to_string()) };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // Ensure each board has its own entries, owner and digest.
+        let msg = QueryMsg::GetHash { board: None };
+        let value: HashResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.hash, r#"{"1":10}"#);
+        let msg = QueryMsg::GetHash { board: Some("weekly".to_string()) };
+        let value: HashResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.hash, r#"{"1":18}"#);
+        let msg = QueryMsg::GetOwner { board: Some("weekly".to_string()) };
+        let value: OwnerResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.owner, Some(Addr::unchecked("bob")));
+        let msg = QueryMsg::StateDigest { board: Some("weekly".to_string()) };
+        let value: StateDigestResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.digest, digest_of(&[("1", 18)]));
+
+        // Ensure unknown boards are reported.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 1, memo: None, board: Some("daily".to_string()) };
+        match execute_as(deps.as_mut(), "owner", msg) {
+            Err(ContractError::BoardNotFound { board }) => assert_eq!(board, "daily"),
+            _ => panic!("Must return board not found error"),
         }
+        assert!(query(deps.as_ref(), mock_env(), QueryMsg::GetHash { board: Some("daily".to_string()) }).is_err());
+
+        // Ensure ListBoards lists both boards by name.
+        let msg = QueryMsg::ListBoards { start_after: None, limit: None };
+        let value: ListBoardsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        let names: Vec<_> = value.boards.iter().map(|board| board.name.as_str()).collect();
+        assert_eq!(names, vec!["default", "weekly"]);
     }
 
-    /// Testing storing scores for lots of addresses in the smart contract state.
+    /// Ensure closed boards reject execute msgs but still answer queries.
     #[test]
-    fn store_lots_of_addresses_then_set_them() {
+    fn close_board_keeps_queries() {
         // Call the "Do Before Each" testing utility function.
         let (mut deps, info, msg) = setup();
 
-        // Instantiate the contract.
+        // Instantiate the contract and create a board owned by bob.
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
+        let _res = create_board(deps.as_mut(), "weekly", "bob", 17).unwrap();
 
-        for n in 2..20 {
-            // Call AddAddress.
-            let info = mock_info("owner", &coins(1000, "earth"));
-            let new_address = n.to_string();
-            let new_score = n * 10 as i32;
-            let msg = ExecuteMsg::AddAddress { new_address: new_address, new_score: new_score};
-            let _res = execute(deps.as_mut(), mock_env(), info, msg);
+        // Ensure only admins of the board or the default board can close it.
+        match execute_as(deps.as_mut(), "carol", ExecuteMsg::CloseBoard { board: "weekly".to_string() }) {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
         }
-
-        for n in 1..20 {
-            // Ensure Address{n}'s score is n * 10.
-            let address = n.to_string();
-            let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress {address : address}).unwrap();
-            let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
-            assert_eq!(value.score, n * 10 as i32);
+        match execute_as(deps.as_mut(), "owner", ExecuteMsg::CloseBoard { board: "default".to_string() }) {
+            Err(ContractError::CannotCloseDefaultBoard {}) => {}
+            _ => panic!("Must return cannot close default board error"),
         }
+        let _res = execute_as(deps.as_mut(), "bob", ExecuteMsg::CloseBoard { board: "weekly".to_string() }).unwrap();
 
-        for n in 1..20 {
-            // Execute Set as owner.
-            let info = mock_info("owner", &coins(1000, "earth"));
-            let msg = ExecuteMsg::Set { address: n.to_string(), new_score: 100 as i32};
-            let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-        }
-        
-        for n in 1..20 {
-            // Make sure Address1's score is 100.
-            let res = query(deps.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress {address : n.to_string()}).unwrap();
-            let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
-            assert_eq!(value.score, 100 as i32);
+        // Ensure the board rejects execute msgs.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 18, memo: None, board: Some("weekly".to_string()) };
+        match execute_as(deps.as_mut(), "bob", msg) {
+            Err(ContractError::BoardClosed { board }) => assert_eq!(board, "weekly"),
+            _ => panic!("Must return board closed error"),
         }
+
+        // Ensure the board still answers queries and is listed as closed.
+        let msg = QueryMsg::GetScoreFromAddress { address: Some("1".to_string()), include_profile: false, board: Some("weekly".to_string()), name: None };
+        let value: ScoreFromAddressResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.score, 17);
+        let msg = QueryMsg::ListBoards { start_after: Some("default".to_string()), limit: None };
+        let value: ListBoardsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.boards[0].closed_height, Some(mock_env().block.height));
+
+        // Ensure the default board is unaffected.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 11, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
     }
 
-    /// Testing owners of different smart contracts can't Set each other's scores.
+    /// Ensure migrating a contract from before boards makes its data the default board.
     #[test]
-    fn cross_set() {
-        // Mock out dependencies.
-        let mut deps1 = mock_dependencies(&[]);
-        let mut deps2 = mock_dependencies(&[]);
+    fn migrate_creates_default_board() {
+        // Create a legacy contract and migrate it.
+        let mut deps = setup_legacy(r#"{"1":10}"#);
+        let _res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
 
-        // Mock out account info.
-        let info1 = mock_info("Alice", &coins(1000, "earth"));
-        let info2 = mock_info("Bob", &coins(2, "token"));
+        // Ensure the default board exists and holds the scores.
+        let msg = QueryMsg::ListBoards { start_after: None, limit: None };
+        let value: ListBoardsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.boards.len(), 1);
+        assert_eq!(value.boards[0].name, DEFAULT_BOARD);
+        let msg = QueryMsg::GetHash { board: Some(DEFAULT_BOARD.to_string()) };
+        let value: HashResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.hash, r#"{"1":10}"#);
+    }
 
-        // Create a message that could be used to instantiate a smart contract.
-        let msg1 = InstantiateMsg {
-            first_address: "1".to_string(),
-            first_address_score: 5 as i32
-        };
-        let msg2 = InstantiateMsg {
-            first_address: "1".to_string(),
-            first_address_score: 17 as i32
-        };
-    
-        // Instantiate both smart contracts.
-        let res1 = instantiate(deps1.as_mut(), mock_env(), info1, msg1).unwrap();
-        assert_eq!(0, res1.messages.len());
-        let res2 = instantiate(deps2.as_mut(), mock_env(), info2, msg2).unwrap();
-        assert_eq!(0, res2.messages.len());
+    /// Ensure pausing a board blocks closing it, but leaves other boards and names alone.
+    #[test]
+    fn pause_board_blocks_close_board() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
 
-        // Reset the infos because they were moved.
-        let info1 = mock_info("Alice", &coins(1000, "earth"));
-        let info2 = mock_info("Bob", &coins(2, "token"));
+        // Instantiate the contract, create a board owned by bob and pause it.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+        let _res = create_board(deps.as_mut(), "weekly", "bob", 17).unwrap();
+        let msg = ExecuteMsg::Pause { reason: "bad import".to_string(), board: Some("weekly".to_string()) };
+        let _res = execute_as(deps.as_mut(), "bob", msg).unwrap();
 
-        // Try to execute Set on Alice's contract as Bob.
-        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 0 as i32};
-        let res = execute(deps1.as_mut(), mock_env(), info2, msg);
-        match res {
-            Err(ContractError::Unauthorized {}) => {}
-            _ => panic!("Must return unauthorized error"),
+        // Ensure neither bob nor the owner can close the paused board.
+        for sender in ["bob", "owner"].iter() {
+            match execute_as(deps.as_mut(), sender, ExecuteMsg::CloseBoard { board: "weekly".to_string() }) {
+                Err(ContractError::ContractPaused { reason }) => assert_eq!(reason, "bad import"),
+                _ => panic!("Must return contract paused error"),
+            }
         }
 
-        // Ensure Alice's Address1 score is still 5.
-        let res = query(deps1.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress {address : "1".to_string()}).unwrap();
-        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
-        assert_eq!(value.score, 5 as i32);
+        // Ensure the default board and the name registry still work.
+        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 11, memo: None, board: None };
+        let _res = execute_as(deps.as_mut(), "owner", msg).unwrap();
+        let _res = execute_as(deps.as_mut(), "bob", ExecuteMsg::RegisterName { name: "bobby".to_string() }).unwrap();
+    }
 
-        // Try to execute Set on Bob's contract as Alice.
-        let msg = ExecuteMsg::Set { address: "1".to_string(), new_score: 0 as i32};
-        let res = execute(deps2.as_mut(), mock_env(), info1, msg);
-        match res {
-            Err(ContractError::Unauthorized {}) => {}
-            _ => panic!("Must return unauthorized error"),
-        }
+    /// Ensure msgs that are not scoped to a board are rejected if they reach one.
+    #[test]
+    fn execute_board_rejects_unscoped_msgs() {
+        // Call the "Do Before Each" testing utility function.
+        let (mut deps, info, msg) = setup();
 
-        // Ensure Bob's Address1 score is still 17.
-        let res = query(deps2.as_ref(), mock_env(), QueryMsg::GetScoreFromAddress {address : "1".to_string()}).unwrap();
-        let value: ScoreFromAddressResponse = from_binary(&res).unwrap();
-        assert_eq!(value.score, 17 as i32);
+        // Instantiate the contract.
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Ensure execute_board errors instead of running the msg.
+        let info = mock_info("owner", &coins(1000, "earth"));
+        let msg = ExecuteMsg::CloseBoard { board: "weekly".to_string() };
+        match execute_board(deps.as_mut(), mock_env(), info, msg) {
+            Err(ContractError::NotScopedToBoard {}) => {}
+            _ => panic!("Must return not scoped to board error"),
+        }
     }
 }
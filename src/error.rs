@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{OverflowError, StdError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,6 +8,111 @@ pub enum ContractError {
 
     #[error("Unauthorized")]
     Unauthorized {},
+
+    #[error("Address already exists: {address}")]
+    AddressAlreadyExists { address: String },
+
+    #[error("Duplicate addresses: {}", .addresses.join(", "))]
+    DuplicateAddresses { addresses: Vec<String> },
+
+    #[error("Address not found: {address}")]
+    AddressNotFound { address: String },
+
+    #[error("Invalid address: {address:?}")]
+    InvalidAddress { address: String },
+
+    #[error("Self-registered addresses must start with a score of {expected}")]
+    InvalidRegistrationScore { expected: i32 },
+
+    #[error("Score must be between {min} and {max}")]
+    ScoreOutOfRange { min: i32, max: i32 },
+
+    #[error("Change of {delta} exceeds the maximum of {max}")]
+    DeltaTooLarge { delta: u32, max: u32 },
+
+    #[error("Memo of {length} bytes exceeds the maximum of {max}")]
+    MemoTooLong { length: u64, max: u32 },
+
+    #[error("Profile {field} of {length} bytes exceeds the maximum of {max}")]
+    ProfileFieldTooLong { field: String, length: u64, max: u32 },
+
+    #[error("Profile has {count} tags, above the maximum of {max}")]
+    TooManyProfileTags { count: u64, max: u32 },
+
+    #[error("Duplicate profile tag: {key}")]
+    DuplicateProfileTag { key: String },
+
+    #[error("Invalid name {name}: {reason}")]
+    InvalidName { name: String, reason: String },
+
+    #[error("Name is already taken: {name}")]
+    NameTaken { name: String },
+
+    #[error("Board not found: {board}")]
+    BoardNotFound { board: String },
+
+    #[error("Board already exists: {board}")]
+    BoardAlreadyExists { board: String },
+
+    #[error("Board is closed: {board}")]
+    BoardClosed { board: String },
+
+    #[error("The default board cannot be closed")]
+    CannotCloseDefaultBoard {},
+
+    #[error("Msg is not scoped to a board")]
+    NotScopedToBoard {},
+
+    #[error("Invalid config: {reason}")]
+    InvalidConfig { reason: String },
+
+    #[error("Score of {address} would overflow: {source}")]
+    ScoreOverflow { address: String, source: OverflowError },
+
+    #[error("Unsupported score operation: {operation}")]
+    UnsupportedOperation { operation: String },
+
+    #[error("Batch of {size} operations exceeds the maximum of {max}")]
+    BatchTooLarge { size: u64, max: u32 },
+
+    #[error("Batch operation {index} failed: {source}")]
+    BatchOperationFailed { index: u64, source: Box<ContractError> },
+
+    #[error("Hook already registered: {address}")]
+    HookAlreadyRegistered { address: String },
+
+    #[error("Hook not registered: {address}")]
+    HookNotRegistered { address: String },
+
+    #[error("Contract is paused: {reason}")]
+    ContractPaused { reason: String },
+
+    #[error("Contract is not paused")]
+    NotPaused {},
+
+    #[error("No ownership transfer is pending")]
+    NoPendingOwner {},
+
+    #[error("The ownership transfer has expired")]
+    OwnershipProposalExpired {},
+
+    #[error("Role {role} was already granted to {address}")]
+    RoleAlreadyGranted { role: String, address: String },
+
+    #[error("Role {role} was not granted to {address}")]
+    RoleNotGranted { role: String, address: String },
+
+    #[error("Cannot migrate from a different contract type: {previous_contract}")]
+    CannotMigrate { previous_contract: String },
+
+    #[error("Cannot migrate from newer version ({previous_version}) to older version ({new_version})")]
+    CannotMigrateVersion { previous_version: String, new_version: String },
+
+    #[error("Invalid contract version: {version}")]
+    InvalidVersion { version: String },
+
+    #[error("Invalid legacy state: {reason}")]
+    InvalidLegacyState { reason: String },
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
 }
@@ -2,10 +2,13 @@
 // Imports
 // ======================================================================
 
-use cosmwasm_std::Addr;
+use cosmwasm_std::{to_binary, Addr, Binary, CosmosMsg, StdResult, WasmMsg};
+use cw0::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::state::{BoardInfo, HistoryRecord, HookKind, KeyKind, PauseInfo, Profile, Role};
+
 // ======================================================================
 // Message Block
 // ======================================================================
@@ -14,23 +17,345 @@ use serde::{Deserialize, Serialize};
 /// be used to instantiate a smart contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    /// The smart contract's initial address.
-    pub first_address: String,
+    /// The smart contract's initial address, if any. It is stored
+    /// ahead of initial_scores.
+    #[serde(default)]
+    pub first_address: Option<String>,
 
     /// The score cooresponding to the smart contract's initial address.
+    #[serde(default)]
     pub first_address_score: i32 ,
+
+    /// The rest of the initial (address, score) pairs. Every address must
+    /// be valid and unique after normalization, and every score must be
+    /// within the configured bounds.
+    #[serde(default)]
+    pub initial_scores: Vec<(String, i32)>,
+
+    /// The owner of the smart contract. Defaults to the sender, so a
+    /// factory contract can instantiate on behalf of someone else.
+    #[serde(default)]
+    pub owner: Option<String>,
+
+    /// Whether keys that are not valid addresses may be stored as
+    /// external identifiers. Addresses are rejected when they fail
+    /// validation unless this is set.
+    #[serde(default)]
+    pub allow_external_keys: bool,
+
+    /// Whether anyone may call AddAddress to register their own
+    /// address. Only the owner may add addresses unless this is set.
+    #[serde(default)]
+    pub open_registration: bool,
+
+    /// The score every self-registered address starts with, and the
+    /// score GetScoreFromAddress reports for addresses that are not stored.
+    #[serde(default)]
+    pub default_score: i32,
+
+    /// The most operations a single BatchUpdate may carry.
+    /// Defaults to DEFAULT_MAX_BATCH_SIZE.
+    #[serde(default)]
+    pub max_batch_size: Option<u32>,
+
+    /// The lowest score any write may store. Defaults to i32::MIN.
+    #[serde(default)]
+    pub min_score: Option<i32>,
+
+    /// The highest score any write may store. Defaults to i32::MAX.
+    #[serde(default)]
+    pub max_score: Option<i32>,
+
+    /// The largest change a single Set, Increment or Decrement
+    /// may make to a score. Defaults to no limit.
+    #[serde(default)]
+    pub max_delta: Option<u32>,
+
+    /// The most history records kept per address.
+    /// Defaults to DEFAULT_MAX_HISTORY.
+    #[serde(default)]
+    pub max_history: Option<u32>,
 }
 
+/// The blueprint for a message that will be used to
+/// migrate a smart contract to a newer version.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 /// The blueprint for a message that will be used to execute
 /// a command that will edit the contents of a smart contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     /// Outline the blueprint for a ExecuteMsg::Set(...).
-    Set { address: String, new_score: i32 },
+    /// Every write takes an optional memo that is kept in the history.
+    Set {
+        #[serde(default)]
+        board: Option<String>,
+        address: String,
+        new_score: i32,
+        #[serde(default)]
+        memo: Option<String>,
+    },
 
     /// Outline the blueprint for a ExecuteMsg::AddAddress(...).
-    AddAddress { new_address: String, new_score: i32 },
+    AddAddress {
+        #[serde(default)]
+        board: Option<String>,
+        new_address: String,
+        new_score: i32,
+        #[serde(default)]
+        memo: Option<String>,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::SetProfile(...).
+    /// Replaces the profile of a stored key. The address defaults to the
+    /// sender, and only admins may set the profile of another key. An
+    /// empty profile removes it.
+    SetProfile {
+        #[serde(default)]
+        board: Option<String>,
+        #[serde(default)]
+        address: Option<String>,
+        profile: Profile,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::RegisterName(...).
+    /// Claims a unique name for the sender, releasing any name the
+    /// sender registered before.
+    RegisterName { name: String },
+
+    /// Outline the blueprint for a ExecuteMsg::RemoveAddress(...).
+    RemoveAddress {
+        #[serde(default)]
+        board: Option<String>,
+        address: String,
+        #[serde(default)]
+        memo: Option<String>,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::Increment(...).
+    /// With register_missing, an unknown address is first added
+    /// with the default score, which needs the registrar role.
+    Increment {
+        #[serde(default)]
+        board: Option<String>,
+        address: String,
+        delta: u32,
+        #[serde(default)]
+        register_missing: bool,
+        #[serde(default)]
+        memo: Option<String>,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::Decrement(...).
+    /// With register_missing, an unknown address is first added
+    /// with the default score, which needs the registrar role.
+    Decrement {
+        #[serde(default)]
+        board: Option<String>,
+        address: String,
+        delta: u32,
+        #[serde(default)]
+        register_missing: bool,
+        #[serde(default)]
+        memo: Option<String>,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::BatchUpdate(...).
+    /// Applies every operation in order, or none of them if one fails.
+    BatchUpdate {
+        #[serde(default)]
+        board: Option<String>,
+        updates: Vec<ScoreOp>,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::UpdateConfig(...).
+    /// Only admins may update the config. Fields left as None keep their
+    /// value. Stored scores outside new bounds are kept as they are.
+    UpdateConfig {
+        #[serde(default)]
+        board: Option<String>,
+        open_registration: Option<bool>,
+        default_score: Option<i32>,
+        max_batch_size: Option<u32>,
+        min_score: Option<i32>,
+        max_score: Option<i32>,
+        max_delta: Option<u32>,
+        max_history: Option<u32>,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::AddHook(...).
+    /// Only admins may add hooks. A Member hook gets a cw4 MemberChangedHookMsg
+    /// whenever a member weight changes, and a Score hook gets a
+    /// ScoreChangedHookMsg whenever any score is written.
+    AddHook {
+        #[serde(default)]
+        board: Option<String>,
+        addr: String,
+        #[serde(default)]
+        kind: HookKind,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::RemoveHook(...).
+    /// Only admins may remove hooks.
+    RemoveHook {
+        #[serde(default)]
+        board: Option<String>,
+        addr: String,
+        #[serde(default)]
+        kind: HookKind,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::Pause(...).
+    /// Only admins may pause. While paused, every message except
+    /// Unpause and the ownership transfer messages fails.
+    Pause {
+        #[serde(default)]
+        board: Option<String>,
+        reason: String,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::Unpause().
+    /// Only admins may unpause.
+    Unpause {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::ProposeOwner(...).
+    /// Ownership only moves once new_owner calls AcceptOwnership.
+    ProposeOwner {
+        #[serde(default)]
+        board: Option<String>,
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::AcceptOwnership().
+    AcceptOwnership {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::CancelOwnershipProposal().
+    CancelOwnershipProposal {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::RenounceOwnership().
+    /// This leaves the contract without an owner for good.
+    RenounceOwnership {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::GrantRole(...).
+    /// Only the owner may grant Admin. Admins may grant the other roles.
+    GrantRole {
+        #[serde(default)]
+        board: Option<String>,
+        role: Role,
+        address: String,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::RevokeRole(...).
+    /// Only the owner may revoke Admin. Admins may revoke the other roles.
+    RevokeRole {
+        #[serde(default)]
+        board: Option<String>,
+        role: Role,
+        address: String,
+    },
+
+    /// Outline the blueprint for a ExecuteMsg::CreateBoard(...).
+    /// Only admins of the default board may create boards. The new board
+    /// is set up exactly like a new contract instantiated with msg.
+    CreateBoard { board: String, msg: InstantiateMsg },
+
+    /// Outline the blueprint for a ExecuteMsg::CloseBoard(...).
+    /// Only admins of the board or of the default board may close it. A
+    /// closed board keeps answering queries but rejects every execute msg.
+    CloseBoard { board: String },
+}
+
+impl ExecuteMsg {
+    /// Returns the board the msg is scoped to, or None for the default
+    /// board. RegisterName, CreateBoard and CloseBoard are handled outside
+    /// of any board, so they return None.
+    pub fn board(&self) -> Option<&str> {
+        match self {
+            ExecuteMsg::Set { board, .. }
+            | ExecuteMsg::AddAddress { board, .. }
+            | ExecuteMsg::SetProfile { board, .. }
+            | ExecuteMsg::RemoveAddress { board, .. }
+            | ExecuteMsg::Increment { board, .. }
+            | ExecuteMsg::Decrement { board, .. }
+            | ExecuteMsg::BatchUpdate { board, .. }
+            | ExecuteMsg::UpdateConfig { board, .. }
+            | ExecuteMsg::AddHook { board, .. }
+            | ExecuteMsg::RemoveHook { board, .. }
+            | ExecuteMsg::Pause { board, .. }
+            | ExecuteMsg::Unpause { board, .. }
+            | ExecuteMsg::ProposeOwner { board, .. }
+            | ExecuteMsg::AcceptOwnership { board, .. }
+            | ExecuteMsg::CancelOwnershipProposal { board, .. }
+            | ExecuteMsg::RenounceOwnership { board, .. }
+            | ExecuteMsg::GrantRole { board, .. }
+            | ExecuteMsg::RevokeRole { board, .. } => board.as_deref(),
+            ExecuteMsg::RegisterName { .. } | ExecuteMsg::CreateBoard { .. } | ExecuteMsg::CloseBoard { .. } => None,
+        }
+    }
+}
+
+/// The blueprint for a single operation inside a BatchUpdate.
+/// Each operation needs the same role as the matching ExecuteMsg.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreOp {
+    /// Works like ExecuteMsg::Set(...).
+    Set {
+        address: String,
+        new_score: i32,
+        #[serde(default)]
+        memo: Option<String>,
+    },
+
+    /// Works like ExecuteMsg::AddAddress(...).
+    Add {
+        new_address: String,
+        new_score: i32,
+        #[serde(default)]
+        memo: Option<String>,
+    },
+
+    /// Works like ExecuteMsg::RemoveAddress(...).
+    Remove {
+        address: String,
+        #[serde(default)]
+        memo: Option<String>,
+    },
+
+    /// Works like ExecuteMsg::Increment(...).
+    Increment {
+        address: String,
+        delta: u32,
+        #[serde(default)]
+        register_missing: bool,
+        #[serde(default)]
+        memo: Option<String>,
+    },
+
+    /// Works like ExecuteMsg::Decrement(...).
+    Decrement {
+        address: String,
+        delta: u32,
+        #[serde(default)]
+        register_missing: bool,
+        #[serde(default)]
+        memo: Option<String>,
+    },
 }
 
 /// The blueprint for a message that will be used to execute
@@ -40,13 +365,251 @@ pub enum ExecuteMsg {
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     /// Outline the blueprint for a QueryMsg::GetOwner().
-    GetOwner {},
+    GetOwner {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::PendingOwner().
+    PendingOwner {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::PauseStatus().
+    PauseStatus {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::ListRoleMembers(...).
+    /// Lists the addresses the role was granted to, ordered by address.
+    ListRoleMembers {
+        #[serde(default)]
+        board: Option<String>,
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::HasRole(...).
+    /// Also true for the owner, and for admins unless the role is Admin.
+    HasRole {
+        #[serde(default)]
+        board: Option<String>,
+        role: Role,
+        address: String,
+    },
+
+    /// Outline the blueprint for a QueryMsg::GetConfig().
+    GetConfig {
+        #[serde(default)]
+        board: Option<String>,
+    },
 
     /// Outline the blueprint for a QueryMsg::GetHash().
-    GetHash {},
+    /// Returns the whole table as a JSON String with keys in sorted order.
+    GetHash {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::StateDigest().
+    /// Returns the Merkle root of the whole table.
+    StateDigest {
+        #[serde(default)]
+        board: Option<String>,
+    },
 
     /// Outline the blueprint for a QueryMsg::GetScoreFromAddress(...).
-    GetScoreFromAddress { address: String },
+    /// Takes exactly one of address or a registered name.
+    /// Set include_profile to also return the profile of the address.
+    GetScoreFromAddress {
+        #[serde(default)]
+        board: Option<String>,
+        #[serde(default)]
+        address: Option<String>,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        include_profile: bool,
+    },
+
+    /// Outline the blueprint for a QueryMsg::ListScores(...).
+    /// Entries are ordered by address. Pass the last address of a
+    /// page as start_after to get the next page.
+    ListScores {
+        #[serde(default)]
+        board: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        #[serde(default)]
+        include_profile: bool,
+    },
+
+    /// Outline the blueprint for a QueryMsg::TopScores(...).
+    /// Entries are ordered from the highest score down. Equal scores
+    /// are ordered by insertion: the address added first ranks higher.
+    TopScores {
+        #[serde(default)]
+        board: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        #[serde(default)]
+        include_profile: bool,
+    },
+
+    /// Outline the blueprint for a QueryMsg::BottomScores(...).
+    /// Entries are ordered exactly opposite to TopScores.
+    BottomScores {
+        #[serde(default)]
+        board: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        #[serde(default)]
+        include_profile: bool,
+    },
+
+    /// Outline the blueprint for a QueryMsg::Rank(...).
+    /// Takes exactly one of address or a registered name.
+    Rank {
+        #[serde(default)]
+        board: Option<String>,
+        #[serde(default)]
+        address: Option<String>,
+        #[serde(default)]
+        name: Option<String>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::ScoreAtHeight(...).
+    /// Returns the score at the start of the given block,
+    /// before any change made in that block. Takes exactly one of
+    /// address or a registered name.
+    ScoreAtHeight {
+        #[serde(default)]
+        board: Option<String>,
+        #[serde(default)]
+        address: Option<String>,
+        #[serde(default)]
+        name: Option<String>,
+        height: u64,
+    },
+
+    /// Outline the blueprint for a QueryMsg::TotalAtHeight(...).
+    /// Returns the sum of every score at the start of the given block,
+    /// before any change made in that block.
+    TotalAtHeight {
+        #[serde(default)]
+        board: Option<String>,
+        height: u64,
+    },
+
+    /// Outline the blueprint for a QueryMsg::Admin().
+    /// Part of the cw4 interface. Returns the owner.
+    Admin {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::TotalWeight().
+    /// Part of the cw4 interface. Returns the sum of every member weight.
+    TotalWeight {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::ListMembers(...).
+    /// Part of the cw4 interface. Members are ordered by address.
+    ListMembers {
+        #[serde(default)]
+        board: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::Member(...).
+    /// Part of the cw4 interface. Only addresses are members, never
+    /// external keys, and a negative score counts as a weight of 0.
+    Member {
+        #[serde(default)]
+        board: Option<String>,
+        addr: String,
+        at_height: Option<u64>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::Hooks().
+    /// Part of the cw4 interface. Lists the Member hooks.
+    Hooks {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::ListHooks().
+    /// Lists every hook of every kind, ordered by kind then address.
+    ListHooks {
+        #[serde(default)]
+        board: Option<String>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::ScoreHistory(...).
+    /// Records are ordered from oldest to newest. Pass the id of the
+    /// last record of a page as start_after to get the next page.
+    /// Takes exactly one of address or a registered name.
+    ScoreHistory {
+        #[serde(default)]
+        board: Option<String>,
+        #[serde(default)]
+        address: Option<String>,
+        #[serde(default)]
+        name: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Outline the blueprint for a QueryMsg::ListBoards(...).
+    /// Boards are ordered by name, and include closed boards.
+    ListBoards { start_after: Option<String>, limit: Option<u32> },
+
+    /// Outline the blueprint for a QueryMsg::ResolveName(...).
+    /// Returns the address a name was registered by, if any.
+    ResolveName { name: String },
+
+    /// Outline the blueprint for a QueryMsg::ReverseLookup(...).
+    /// Returns the name an address registered, if any.
+    ReverseLookup { address: String },
+}
+
+impl QueryMsg {
+    /// Returns the board the msg is scoped to, or None for the default
+    /// board. ListBoards, ResolveName and ReverseLookup are answered
+    /// outside of any board, so they return None.
+    pub fn board(&self) -> Option<&str> {
+        match self {
+            QueryMsg::GetOwner { board, .. }
+            | QueryMsg::PendingOwner { board, .. }
+            | QueryMsg::PauseStatus { board, .. }
+            | QueryMsg::ListRoleMembers { board, .. }
+            | QueryMsg::HasRole { board, .. }
+            | QueryMsg::GetConfig { board, .. }
+            | QueryMsg::GetHash { board, .. }
+            | QueryMsg::StateDigest { board, .. }
+            | QueryMsg::GetScoreFromAddress { board, .. }
+            | QueryMsg::ListScores { board, .. }
+            | QueryMsg::TopScores { board, .. }
+            | QueryMsg::BottomScores { board, .. }
+            | QueryMsg::Rank { board, .. }
+            | QueryMsg::ScoreAtHeight { board, .. }
+            | QueryMsg::TotalAtHeight { board, .. }
+            | QueryMsg::Admin { board, .. }
+            | QueryMsg::TotalWeight { board, .. }
+            | QueryMsg::ListMembers { board, .. }
+            | QueryMsg::Member { board, .. }
+            | QueryMsg::Hooks { board, .. }
+            | QueryMsg::ListHooks { board, .. }
+            | QueryMsg::ScoreHistory { board, .. } => board.as_deref(),
+            QueryMsg::ListBoards { .. } | QueryMsg::ResolveName { .. } | QueryMsg::ReverseLookup { .. } => None,
+        }
+    }
 }
 
 // ======================================================================
@@ -57,23 +620,287 @@ pub enum QueryMsg {
 /// the smart contract owner's name.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct OwnerResponse {
-    /// The name of the smart contract's owner.
-    pub owner: Addr,
+    /// The name of the smart contract's owner,
+    /// or None if ownership has been renounced.
+    pub owner: Option<Addr>,
 }
 
-/// The blueprint for a response that contains A HashMap
+/// The blueprint for a response that contains
+/// the ownership transfer waiting to be accepted.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingOwnerResponse {
+    /// The address that may accept ownership, if any.
+    pub new_owner: Option<Addr>,
+
+    /// When the proposal stops being acceptable, if there is one.
+    pub expiry: Option<Expiration>,
+}
+
+/// The blueprint for a response that contains
+/// whether the contract is paused.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseStatusResponse {
+    /// Whether the contract is paused.
+    pub paused: bool,
+
+    /// Who paused the contract, when and why, if it is paused.
+    pub pause: Option<PauseInfo>,
+}
+
+/// The blueprint for a response that contains a map
 /// of addresses and cooresponding scores converted to
 /// a JSON String correspond to the provided smart contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct HashResponse {
-    /// A HashMap of addresses and cooresponding scores converted to a JSON String.
+    /// A map of addresses and cooresponding scores converted to a JSON String,
+    /// with the addresses in sorted order.
     pub hash: String,
 }
 
+/// The blueprint for a response that contains
+/// the Merkle root of every (address, score) pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateDigestResponse {
+    /// The root hash of a binary Merkle Patricia trie, as 64 hex characters,
+    /// or all zeros for an empty table. Pairs are sorted by sha256(address),
+    /// and each one is hashed as sha256(0x00 || score || address), where
+    /// score is 4 big-endian bytes. Each branch splits its pairs on the first
+    /// bit of sha256(address) where they differ, and is hashed as
+    /// sha256(0x01 || bit || left || right), where bit is 2 big-endian bytes.
+    /// A table with a single pair has that pair's hash as its root.
+    pub digest: String,
+}
+
 /// The blueprint for a response that contains the score that
 /// corresponds to the provided address and smart contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ScoreFromAddressResponse {
-    /// The score from a corresponding address in the state HashMap.
+    /// The key the score was read from, after normalization or name lookup.
+    pub address: String,
+
+    /// The score from a corresponding address in the state HashMap,
+    /// or the default score if the address is not stored.
     pub score: i32,
+
+    /// Whether the address is stored.
+    pub exists: bool,
+
+    /// The kind of key the score is stored under, if it is stored.
+    pub kind: Option<KeyKind>,
+
+    /// The profile of the address, if it was requested and is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Profile>,
+}
+
+/// The blueprint for a single (address, score) entry.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScoreEntry {
+    /// The address of the entry.
+    pub address: String,
+
+    /// The score at the address.
+    pub score: i32,
+
+    /// The kind of key the score is stored under.
+    pub kind: KeyKind,
+
+    /// The profile of the entry, if it was requested and is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Profile>,
+}
+
+/// The blueprint for a response that contains
+/// one page of entries ordered by address.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListScoresResponse {
+    /// The entries in this page.
+    pub scores: Vec<ScoreEntry>,
+}
+
+/// The blueprint for a response that contains the leaderboard
+/// position of an address.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RankResponse {
+    /// The address that was ranked.
+    pub address: String,
+
+    /// The score at the address.
+    pub score: i32,
+
+    /// The position of the address in TopScores, starting at 1.
+    pub rank: u64,
+}
+
+/// The blueprint for a response that contains one page
+/// of the addresses a role was granted to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleMembersResponse {
+    /// The addresses in this page.
+    pub members: Vec<Addr>,
+}
+
+/// The blueprint for a response that contains
+/// whether an address holds a role.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HasRoleResponse {
+    /// Whether the address holds the role.
+    pub has_role: bool,
+}
+
+/// The blueprint for a response that contains
+/// the settings of the smart contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// Whether keys that are not valid addresses may be stored
+    /// as external identifiers instead of being rejected.
+    pub allow_external_keys: bool,
+
+    /// Whether anyone may register their own address with AddAddress.
+    pub open_registration: bool,
+
+    /// The score every self-registered address starts with, and the
+    /// score reported for addresses that are not stored.
+    pub default_score: i32,
+
+    /// The most operations a single BatchUpdate may carry.
+    pub max_batch_size: u32,
+
+    /// The lowest score any write may store.
+    pub min_score: i32,
+
+    /// The highest score any write may store.
+    pub max_score: i32,
+
+    /// The largest change a single Set, Increment or Decrement may
+    /// make to a score.
+    pub max_delta: u32,
+
+    /// The most history records kept per address.
+    pub max_history: u32,
+}
+
+/// The blueprint for a response that contains one page
+/// of the history of an address.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScoreHistoryResponse {
+    /// The address the history belongs to.
+    pub address: String,
+
+    /// The records in this page.
+    pub records: Vec<HistoryRecord>,
+}
+
+/// The blueprint for a response that contains one page of boards.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListBoardsResponse {
+    /// The boards in this page.
+    pub boards: Vec<BoardInfo>,
+}
+
+/// The blueprint for a response that contains
+/// the address a name belongs to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ResolveNameResponse {
+    /// The name after normalization.
+    pub name: String,
+
+    /// The address that registered the name, if any.
+    pub address: Option<Addr>,
+}
+
+/// The blueprint for a response that contains
+/// the name an address registered.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReverseLookupResponse {
+    /// The address that was looked up.
+    pub address: String,
+
+    /// The name the address registered, if any.
+    pub name: Option<String>,
+}
+
+/// The blueprint for a response that contains the score
+/// of an address at a past block height.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScoreAtHeightResponse {
+    /// The address that was looked up.
+    pub address: String,
+
+    /// The block height that was looked up.
+    pub height: u64,
+
+    /// The score at the address, or None if it was not stored.
+    pub score: Option<i32>,
+}
+
+/// The blueprint for a response that contains the sum
+/// of every score at a past block height.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalAtHeightResponse {
+    /// The block height that was looked up.
+    pub height: u64,
+
+    /// The sum of every score stored at the block height.
+    pub total: i64,
+}
+
+/// The blueprint for a single hook and what it is notified of.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HookEntry {
+    /// The address of the hook contract.
+    pub addr: Addr,
+
+    /// What the hook contract is notified of.
+    pub kind: HookKind,
+}
+
+/// The blueprint for a response that contains every hook.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListHooksResponse {
+    /// The hooks, ordered by kind then address.
+    pub hooks: Vec<HookEntry>,
+}
+
+// ======================================================================
+// Hook Block
+// ======================================================================
+
+/// The blueprint for the message every Score hook gets whenever a score
+/// is written. Hook contracts must accept it as
+/// {"score_changed_hook": {"address": ..., "old_score": ..., "new_score": ...}}.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScoreChangedHookMsg {
+    /// The address whose score was written.
+    pub address: String,
+
+    /// The score before the write, or None if the address was added.
+    pub old_score: Option<i32>,
+
+    /// The score after the write, or None if the address was removed.
+    pub new_score: Option<i32>,
+}
+
+impl ScoreChangedHookMsg {
+    /// Serializes the message wrapped in its execute variant.
+    pub fn into_binary(self) -> StdResult<Binary> {
+        to_binary(&ScoreChangedExecuteMsg::ScoreChangedHook(self))
+    }
+
+    /// Returns a message that executes the hook contract with this message.
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: self.into_binary()?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+/// The execute variant hook contracts receive a ScoreChangedHookMsg in.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum ScoreChangedExecuteMsg {
+    ScoreChangedHook(ScoreChangedHookMsg),
 }
@@ -5,8 +5,9 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Binary, Empty, Timestamp};
+use cw0::Expiration;
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy, U64Key};
 
 // ======================================================================
 // State Block
@@ -15,6 +16,430 @@ use cw_storage_plus::Item;
 /// Create a struct to represent a state in a smart contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
+    /// The name of the owner of the smart contract,
+    /// or None once ownership has been renounced.
+    pub owner: Option<Addr>,
+}
+
+impl State {
+    /// Returns true iff the given address is the owner.
+    pub fn is_owner(&self, address: &Addr) -> bool {
+        self.owner.as_ref() == Some(address)
+    }
+}
+
+// Make a constant State to save states (see: contract.rs).
+pub const STATE: Item<State> = Item::new("state");
+
+/// Create a struct to represent an ownership transfer
+/// that is waiting for the new owner to accept it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingOwner {
+    /// The address that may accept ownership.
+    pub new_owner: Addr,
+
+    /// When the proposal stops being acceptable.
+    pub expiry: Expiration,
+}
+
+// Make a constant PendingOwner to save ownership proposals (see: contract.rs).
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+
+/// Create a struct to represent why and by whom the contract was paused.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseInfo {
+    /// The address that paused the contract.
+    pub paused_by: Addr,
+
+    /// The block height the contract was paused at.
+    pub height: u64,
+
+    /// The block time the contract was paused at.
+    pub time: Timestamp,
+
+    /// Why the contract was paused.
+    pub reason: String,
+}
+
+// Make a constant PauseInfo that is saved iff the contract is paused (see: contract.rs).
+pub const PAUSE: Item<PauseInfo> = Item::new("pause");
+
+/// Create an enum to represent the roles that can be granted to an address.
+/// The owner holds every role, and admins hold every role except Admin.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// May grant and revoke the Writer and Registrar roles.
+    Admin,
+
+    /// May update the scores of stored addresses.
+    Writer,
+
+    /// May add any address with any score, and remove addresses.
+    Registrar,
+}
+
+impl Role {
+    /// Returns the name the role is stored under.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Writer => "writer",
+            Role::Registrar => "registrar",
+        }
+    }
+}
+
+// Make a constant Map to save which addresses were granted which role.
+pub const ROLES: Map<(&str, &Addr), Empty> = Map::new("roles");
+
+/// Create a struct to represent the settings chosen at instantiation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Whether keys that are not valid addresses may be stored
+    /// as external identifiers instead of being rejected.
+    pub allow_external_keys: bool,
+
+    /// Whether anyone may register their own address with AddAddress.
+    pub open_registration: bool,
+
+    /// The score every self-registered address starts with, and the
+    /// score reported for addresses that are not stored.
+    pub default_score: i32,
+
+    /// The most operations a single BatchUpdate may carry.
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: u32,
+
+    /// The lowest score any write may store.
+    #[serde(default = "default_min_score")]
+    pub min_score: i32,
+
+    /// The highest score any write may store.
+    #[serde(default = "default_max_score")]
+    pub max_score: i32,
+
+    /// The largest change a single Set, Increment or Decrement may
+    /// make to a score. u32::MAX lets any change through.
+    #[serde(default = "default_max_delta")]
+    pub max_delta: u32,
+
+    /// The most history records kept per address. Older
+    /// records are dropped as new ones are appended.
+    #[serde(default = "default_max_history")]
+    pub max_history: u32,
+}
+
+/// The max_batch_size used when none is chosen, and for
+/// configs saved before batches existed.
+pub const DEFAULT_MAX_BATCH_SIZE: u32 = 100;
+
+fn default_max_batch_size() -> u32 {
+    DEFAULT_MAX_BATCH_SIZE
+}
+
+fn default_min_score() -> i32 {
+    i32::MIN
+}
+
+fn default_max_score() -> i32 {
+    i32::MAX
+}
+
+fn default_max_delta() -> u32 {
+    u32::MAX
+}
+
+/// The max_history used when none is chosen, and for
+/// configs saved before history existed.
+pub const DEFAULT_MAX_HISTORY: u32 = 100;
+
+fn default_max_history() -> u32 {
+    DEFAULT_MAX_HISTORY
+}
+
+// Make a constant Config to save the settings (see: contract.rs).
+pub const CONFIG: Item<Config> = Item::new("config");
+
+// Make a constant SnapshotMap to save the score of each address (see: contract.rs).
+// Every address is stored under its own key, so reading or writing one
+// score costs the same no matter how many addresses are stored. Every
+// change is also logged by block height, so past scores can be read back.
+pub const SCORES: SnapshotMap<&str, i32> = SnapshotMap::new(
+    "scores",
+    "scores__checkpoints",
+    "scores__changelog",
+    Strategy::EveryBlock,
+);
+
+// Make a constant SnapshotMap to save the sum of every score under TOTAL_KEY.
+// cw-storage-plus has no snapshotted Item, so a single key is used instead.
+pub const TOTAL: SnapshotMap<&str, i64> = SnapshotMap::new(
+    "score_total",
+    "score_total__checkpoints",
+    "score_total__changelog",
+    Strategy::EveryBlock,
+);
+
+// The only key TOTAL is saved under.
+pub const TOTAL_KEY: &str = "total";
+
+/// Create an enum to represent what kind of key a score is stored under.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyKind {
+    /// An address validated and normalized through the Api.
+    Address,
+
+    /// An off-chain identifier stored verbatim.
+    External,
+}
+
+// Make a constant Map to save the kind of each key in the scores Map.
+pub const KEY_KINDS: Map<&str, KeyKind> = Map::new("key_kinds");
+
+/// Create a struct to represent the optional metadata of a stored key.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Profile {
+    /// The name to display instead of the key.
+    #[serde(default)]
+    pub display_name: Option<String>,
+
+    /// A link to an avatar, a website or any other resource.
+    #[serde(default)]
+    pub uri: Option<String>,
+
+    /// Free-form (key, value) tags. Each key may appear only once.
+    #[serde(default)]
+    pub tags: Vec<(String, String)>,
+}
+
+// Make a constant Map to save the profile of each key in the scores Map.
+// Keys without a profile have no entry.
+pub const PROFILES: Map<&str, Profile> = Map::new("profiles");
+
+/// Create an enum to represent a child in the digest trie. The digest trie
+/// is a binary Merkle Patricia trie that holds every entry of the scores
+/// Map at the path sha256(key), so a write only rehashes one branch.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestChild {
+    /// A single entry, hashed as sha256(0x00 || score || key),
+    /// where score is 4 big-endian bytes.
+    Leaf { path: Binary, hash: Binary },
+
+    /// A branch that splits on the given bit of the path, hashed as
+    /// sha256(0x01 || bit || left || right), where bit is 2 big-endian
+    /// bytes. Every path below it starts with prefix, which has every bit
+    /// from the split on set to 0.
+    Node { bit: u16, prefix: Binary, hash: Binary },
+}
+
+/// Create a struct to represent a branch of the digest trie. Paths with a
+/// 0 at the split bit go left, and paths with a 1 go right.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DigestNode {
+    pub left: DigestChild,
+    pub right: DigestChild,
+}
+
+// Make a constant Item to save the root of the digest trie.
+// An empty scores Map has no root.
+pub const DIGEST_ROOT: Item<DigestChild> = Item::new("digest_root");
+
+// Make a constant Map to save every branch of the digest trie under digest_node_key.
+pub const DIGEST_NODES: Map<&[u8], DigestNode> = Map::new("digest_nodes");
+
+/// Returns the DIGEST_NODES key of the branch that splits on bit under prefix.
+pub fn digest_node_key(bit: u16, prefix: &[u8]) -> Vec<u8> {
+    let mut key = bit.to_be_bytes().to_vec();
+    key.extend_from_slice(prefix);
+    key
+}
+
+// ======================================================================
+// Leaderboard Block
+// ======================================================================
+
+// Make a constant Item to hand out insertion sequence numbers.
+pub const NEXT_SEQ: Item<u64> = Item::new("next_seq");
+
+// Make a constant Map to save the insertion sequence number of each address.
+pub const ENTRY_SEQ: Map<&str, u64> = Map::new("entry_seq");
+
+// Make a constant Map that indexes every address by its rank_key.
+pub const LEADERBOARD: Map<&[u8], String> = Map::new("leaderboard");
+
+/// Returns the leaderboard key of an entry. Keys sort ascending by score,
+/// then descending by sequence number, so iterating the leaderboard in
+/// descending order yields the highest score first and, among equal
+/// scores, the address that was inserted first.
+pub fn rank_key(score: i32, seq: u64) -> Vec<u8> {
+    // Flip the sign bit so negative scores sort below positive ones.
+    let mut key = ((score as u32) ^ 0x8000_0000).to_be_bytes().to_vec();
+    key.extend_from_slice(&(u64::MAX - seq).to_be_bytes());
+    key
+}
+
+// Make a constant Map that counts the entries under every proper prefix of
+// their rank_key, keyed by rank_count_key. A rank adds up the counts of the
+// prefixes that sort above an entry, so it reads at most 255 counts for each
+// byte of the key, however many addresses are stored.
+pub const RANK_COUNTS: Map<&[u8], u64> = Map::new("rank_counts");
+
+/// Returns the RANK_COUNTS key of a rank_key prefix. It starts with the
+/// prefix length, so a range over prefixes of one length skips longer ones.
+pub fn rank_count_key(prefix: &[u8]) -> Vec<u8> {
+    let mut key = vec![prefix.len() as u8];
+    key.extend_from_slice(prefix);
+    key
+}
+
+// ======================================================================
+// Group Block
+// ======================================================================
+
+// Make a constant SnapshotMap to save the cw4 weight of each member.
+// It uses the cw4 storage keys so group contracts can read it raw.
+// Only KeyKind::Address keys are members, and their weight is their
+// score, or 0 if the score is negative.
+pub const MEMBERS: SnapshotMap<&str, u64> = SnapshotMap::new(
+    cw4::MEMBERS_KEY,
+    cw4::MEMBERS_CHECKPOINTS,
+    cw4::MEMBERS_CHANGELOG,
+    Strategy::EveryBlock,
+);
+
+// Make a constant Item to save the sum of every member weight.
+pub const TOTAL_WEIGHT: Item<u64> = Item::new(cw4::TOTAL_KEY);
+
+/// Create an enum to represent what a hook contract is notified of.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HookKind {
+    /// Gets a cw4 MemberChangedHookMsg whenever a member weight changes.
+    Member,
+
+    /// Gets a ScoreChangedHookMsg whenever any score is written.
+    Score,
+}
+
+impl Default for HookKind {
+    /// Member, so cw4 clients that send AddHook { addr } get cw4 hooks.
+    fn default() -> Self {
+        HookKind::Member
+    }
+}
+
+impl HookKind {
+    /// Returns the name the hook kind is stored under.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookKind::Member => "member",
+            HookKind::Score => "score",
+        }
+    }
+}
+
+// Make a constant Map to save which contracts were added as which kind of hook.
+pub const HOOKS: Map<(&str, &Addr), Empty> = Map::new("hooks");
+
+// ======================================================================
+// Board Block
+// ======================================================================
+
+// The name of the board msgs are scoped to when they name none. Its data
+// lives at the top level of storage, where it was before boards existed.
+pub const DEFAULT_BOARD: &str = "default";
+
+// The namespace every other board keeps its data under. Each board sees
+// the same Items and Maps as the default board, prefixed by its name.
+pub const BOARDS_NAMESPACE: &[u8] = b"boards";
+
+/// Create a struct to represent a board.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BoardInfo {
+    /// The name of the board.
+    pub name: String,
+
+    /// The address that created the board.
+    pub created_by: Addr,
+
+    /// The block height the board was created at.
+    pub created_height: u64,
+
+    /// The block height the board was closed at, if it was closed.
+    pub closed_height: Option<u64>,
+}
+
+// Make a constant Map to save every board by name, including the default board.
+pub const BOARDS: Map<&str, BoardInfo> = Map::new("board_info");
+
+// ======================================================================
+// Name Block
+// ======================================================================
+
+// Make a constant Map to save the address each registered name belongs to.
+// Names are normalized before they are saved (see: contract.rs).
+pub const NAMES: Map<&str, Addr> = Map::new("names");
+
+// Make a constant Map to save the name each address registered, if any.
+// It is always the reverse of NAMES.
+pub const NAME_OF: Map<&Addr, String> = Map::new("name_of");
+
+// ======================================================================
+// History Block
+// ======================================================================
+
+/// Create a struct to represent one change to the score at an address.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HistoryRecord {
+    /// The position of the record in the history of its address, starting at 0.
+    pub id: u64,
+
+    /// The score before the change, or None if the address was added.
+    pub old_score: Option<i32>,
+
+    /// The score after the change, or None if the address was removed.
+    pub new_score: Option<i32>,
+
+    /// The address that made the change.
+    pub sender: Addr,
+
+    /// The block height the change was made at.
+    pub height: u64,
+
+    /// The block time the change was made at.
+    pub time: Timestamp,
+
+    /// The note the sender attached to the change, if any.
+    pub memo: Option<String>,
+}
+
+// Make a constant Map to save the history records of each address by id.
+pub const HISTORY: Map<(&str, U64Key), HistoryRecord> = Map::new("history");
+
+/// Create a struct to represent which history records of an address are kept.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct HistoryRange {
+    /// The id of the oldest record kept.
+    pub first: u64,
+
+    /// The id the next record will get.
+    pub next: u64,
+}
+
+// Make a constant Map to save the HistoryRange of each address.
+pub const HISTORY_RANGE: Map<&str, HistoryRange> = Map::new("history_range");
+
+// ======================================================================
+// Legacy State Block
+// ======================================================================
+
+/// The state layout used by contracts deployed before scores were
+/// moved into their own Map (see: migrate in contract.rs).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyState {
     /// The name of the owner of the smart contract.
     pub owner: Addr,
 
@@ -22,5 +447,6 @@ pub struct State {
     pub hash: String,
 }
 
-// Make a constant State to save states (see: contract.rs).
-pub const STATE: Item<State> = Item::new("state");
+// Make a constant LegacyState to read legacy states. It shares its key
+// with STATE, so it can only be loaded before the migration rewrites it.
+pub const LEGACY_STATE: Item<LegacyState> = Item::new("state");